@@ -0,0 +1,95 @@
+//! Named color constants for quick prototyping.
+//! Whites use the dedicated white channel of SK6812RGBW, optionally tinted with RGB elements.
+
+use crate::led::Led;
+
+/// All LEDs turned off
+pub const BLACK: Led = Led::from_rgbw(0, 0, 0, 0);
+/// White channel only, the most efficient white
+pub const WHITE: Led = Led::from_rgbw(0, 0, 0, 255);
+/// White channel tinted with red and green for warmer (yellowish) light
+pub const WARM_WHITE: Led = Led::from_rgbw(64, 24, 0, 255);
+/// White channel tinted with blue for cooler light
+pub const COOL_WHITE: Led = Led::from_rgbw(0, 16, 48, 255);
+/// All channels fully lit, the brightest (and most power hungry) white
+pub const FULL_WHITE: Led = Led::from_rgbw(255, 255, 255, 255);
+/// Pure red
+pub const RED: Led = Led::from_rgb(255, 0, 0);
+/// Pure green (CSS "lime")
+pub const GREEN: Led = Led::from_rgb(0, 255, 0);
+/// Pure blue
+pub const BLUE: Led = Led::from_rgb(0, 0, 255);
+/// Red and green fully lit
+pub const YELLOW: Led = Led::from_rgb(255, 255, 0);
+/// Green and blue fully lit
+pub const CYAN: Led = Led::from_rgb(0, 255, 255);
+/// Red and blue fully lit
+pub const MAGENTA: Led = Led::from_rgb(255, 0, 255);
+/// CSS orange
+pub const ORANGE: Led = Led::from_rgb(255, 165, 0);
+/// CSS purple
+pub const PURPLE: Led = Led::from_rgb(128, 0, 128);
+/// CSS pink
+pub const PINK: Led = Led::from_rgb(255, 192, 203);
+/// Half-lit RGB gray
+pub const GRAY: Led = Led::from_rgb(128, 128, 128);
+/// CSS maroon (dark red)
+pub const MAROON: Led = Led::from_rgb(128, 0, 0);
+/// CSS navy (dark blue)
+pub const NAVY: Led = Led::from_rgb(0, 0, 128);
+/// CSS teal (dark cyan)
+pub const TEAL: Led = Led::from_rgb(0, 128, 128);
+/// CSS olive (dark yellow)
+pub const OLIVE: Led = Led::from_rgb(128, 128, 0);
+/// CSS gold
+pub const GOLD: Led = Led::from_rgb(255, 215, 0);
+/// CSS indigo
+pub const INDIGO: Led = Led::from_rgb(75, 0, 130);
+/// CSS violet
+pub const VIOLET: Led = Led::from_rgb(238, 130, 238);
+
+// Get the color for CSS color name (case-insensitive)
+pub(crate) fn from_name(name: &str) -> Option<Led> {
+    let color = match name.to_ascii_lowercase().as_str() {
+        "black" => BLACK,
+        "white" => WHITE,
+        "red" => RED,
+        // CSS "green" is dark, fully lit green is called "lime"
+        "green" => Led::from_rgb(0, 128, 0),
+        "lime" => GREEN,
+        "blue" => BLUE,
+        "yellow" => YELLOW,
+        "cyan" | "aqua" => CYAN,
+        "magenta" | "fuchsia" => MAGENTA,
+        "orange" => ORANGE,
+        "purple" => PURPLE,
+        "pink" => PINK,
+        "gray" | "grey" => GRAY,
+        "silver" => Led::from_rgb(192, 192, 192),
+        "maroon" => MAROON,
+        "navy" => NAVY,
+        "teal" => TEAL,
+        "olive" => OLIVE,
+        "gold" => GOLD,
+        "indigo" => INDIGO,
+        "violet" => VIOLET,
+        _ => return None,
+    };
+    Some(color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(Led::from_name("red"), Some(Led::from_rgb(255, 0, 0)));
+        assert_eq!(Led::from_name("Orange"), Some(Led::from_rgb(255, 165, 0)));
+        assert_eq!(Led::from_name("grey"), Led::from_name("GRAY"));
+        assert_eq!(Led::from_name("white"), Some(WHITE));
+        assert_eq!(WHITE.w, 255);
+        assert_eq!(Led::from_name("unknown"), None);
+        assert_eq!(Led::from_name(""), None);
+    }
+}
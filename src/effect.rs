@@ -0,0 +1,270 @@
+use crate::led::Led;
+use crate::strip::Strip;
+use palette::Hsv;
+use rand::Rng;
+use std::{ops::Range, time::Duration};
+
+/// Animation that draws consecutive frames on the strip, see `Strip::run_effect`
+pub trait Effect<SPI> {
+    /// Draw the next frame on `strip`. `dt` is the time elapsed since the previous frame.
+    fn step(&mut self, strip: &mut Strip<SPI>, dt: Duration);
+}
+
+/// Rainbow scrolling along the strip with constant speed
+#[derive(Clone, Copy, Debug)]
+pub struct Rainbow {
+    phase: f32,
+    speed: f32,
+    hue_span: f32,
+}
+
+impl Rainbow {
+    /// Create new rainbow spanning `hue_span` degrees along the strip, scrolling by `speed` degrees per second
+    pub fn new(hue_span: f32, speed: f32) -> Self {
+        Self {
+            phase: 0.0,
+            speed,
+            hue_span,
+        }
+    }
+
+    /// Get the hue (in degrees) of the first LED, as drawn by the next step
+    pub fn phase(&self) -> f32 {
+        self.phase
+    }
+}
+
+impl<SPI> Effect<SPI> for Rainbow {
+    fn step(&mut self, strip: &mut Strip<SPI>, dt: Duration) {
+        strip.animate_rainbow(self.phase, self.hue_span);
+        self.phase = (self.phase + self.speed * dt.as_secs_f32()).rem_euclid(360.0);
+    }
+}
+
+/// Block of lit LEDs moving back and forth along the strip, reversing direction at both ends (KITT-style scanner)
+#[derive(Clone, Copy, Debug)]
+pub struct Bouncer {
+    width: usize,
+    color: Led,
+    position: usize,
+    forward: bool,
+}
+
+impl Bouncer {
+    /// Create new bouncer with block of `width` LEDs of `color`, starting at the beginning of the strip
+    pub fn new(width: usize, color: Led) -> Self {
+        Self {
+            width,
+            color,
+            position: 0,
+            forward: true,
+        }
+    }
+
+    /// Get the index of the first LED of the block, as drawn by the next `step`
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Check if the block is moving towards the end of the strip
+    pub fn is_moving_forward(&self) -> bool {
+        self.forward
+    }
+
+    /// Draw the block at current position, turning off the rest of the strip, and move it by one LED
+    pub fn step<SPI>(&mut self, strip: &mut Strip<SPI>) {
+        let last_position = strip.len().saturating_sub(self.width);
+        self.position = self.position.min(last_position);
+
+        strip.clear();
+        strip.fill_range(self.position..self.position + self.width, self.color);
+
+        if self.forward && self.position == last_position {
+            self.forward = false;
+        } else if !self.forward && self.position == 0 {
+            self.forward = true;
+        }
+
+        if last_position > 0 {
+            self.position = if self.forward {
+                self.position + 1
+            } else {
+                self.position - 1
+            };
+        }
+    }
+}
+
+impl<SPI> Effect<SPI> for Bouncer {
+    fn step(&mut self, strip: &mut Strip<SPI>, _dt: Duration) {
+        Bouncer::step(self, strip);
+    }
+}
+
+/// Single meteor of `MeteorShower`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Meteor {
+    /// Position of the head, in LEDs from the beginning of the strip. Negative before the meteor enters the strip.
+    pub position: f32,
+    /// Speed in LEDs per second
+    pub speed: f32,
+    /// Color of the head, the tail fades out from it
+    pub color: Led,
+}
+
+/// Several meteors with fading tails falling along the strip, each respawning with random color and speed
+/// after leaving it. Randomness comes from the provided RNG, so seeded one gives reproducible animation.
+#[derive(Clone, Debug)]
+pub struct MeteorShower<R> {
+    meteors: Vec<Meteor>,
+    tail_length: usize,
+    speed: Range<f32>,
+    rng: R,
+}
+
+impl<R: Rng> MeteorShower<R> {
+    /// Create new shower of `count` meteors with tails of `tail_length` LEDs, moving with random speed from
+    /// `speed` range (in LEDs per second). Meteors start before the beginning of the strip at random distances.
+    pub fn new(count: usize, tail_length: usize, speed: Range<f32>, rng: R) -> Self {
+        let mut shower = Self {
+            meteors: Vec::with_capacity(count),
+            tail_length,
+            speed,
+            rng,
+        };
+        let spread = tail_length.max(1) as f32 * count as f32;
+        shower.meteors = (0..count).map(|_| shower.spawn(spread)).collect();
+        shower
+    }
+
+    /// Get the meteors, as drawn by the next step
+    pub fn meteors(&self) -> &[Meteor] {
+        &self.meteors
+    }
+
+    fn spawn(&mut self, spread: f32) -> Meteor {
+        let speed = if self.speed.is_empty() {
+            self.speed.start
+        } else {
+            self.rng.gen_range(self.speed.clone())
+        };
+        Meteor {
+            position: -self.rng.gen_range(0.0..=spread),
+            speed,
+            color: Hsv::new(self.rng.gen_range(0.0..360.0), 1.0, 1.0).into(),
+        }
+    }
+}
+
+impl<SPI, R: Rng> Effect<SPI> for MeteorShower<R> {
+    fn step(&mut self, strip: &mut Strip<SPI>, dt: Duration) {
+        let len = strip.len();
+        strip.clear();
+
+        for meteor in &self.meteors {
+            let head = meteor.position.floor() as isize;
+            for offset in 0..self.tail_length {
+                let index = head - offset as isize;
+                if let Some(led) = usize::try_from(index)
+                    .ok()
+                    .and_then(|i| strip.leds.get_mut(i))
+                {
+                    let brightness = 1.0 - offset as f32 / self.tail_length as f32;
+                    *led += meteor.color * brightness;
+                }
+            }
+        }
+
+        let spread = len.max(1) as f32;
+        for index in 0..self.meteors.len() {
+            let meteor = &mut self.meteors[index];
+            meteor.position += meteor.speed * dt.as_secs_f32();
+            if meteor.position - self.tail_length as f32 >= len as f32 {
+                self.meteors[index] = self.spawn(spread);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bouncer_reverses_at_ends() {
+        let color = Led::from_rgb(255, 0, 0);
+        let mut strip = Strip::new_mock(5);
+        let mut bouncer = Bouncer::new(2, color);
+
+        let mut positions = Vec::new();
+        for _ in 0..8 {
+            positions.push(bouncer.position());
+            bouncer.step(&mut strip);
+            assert_eq!(strip.count_lit(), 2);
+        }
+
+        assert_eq!(positions, [0, 1, 2, 3, 2, 1, 0, 1]);
+        assert!(bouncer.is_moving_forward());
+        assert_eq!(strip.leds[1..3], [color; 2]);
+    }
+
+    #[test]
+    fn test_bouncer_wider_than_strip() {
+        let mut strip = Strip::new_mock(2);
+        let mut bouncer = Bouncer::new(4, Led::from_rgb(0, 255, 0));
+
+        bouncer.step(&mut strip);
+        bouncer.step(&mut strip);
+        assert_eq!(bouncer.position(), 0);
+        assert_eq!(strip.count_lit(), 2);
+    }
+
+    #[test]
+    fn test_rainbow_effect() {
+        let mut strip = Strip::new_mock(3);
+        let mut rainbow = Rainbow::new(360.0, 90.0);
+
+        Effect::step(&mut rainbow, &mut strip, Duration::from_secs(1));
+        assert_eq!(strip.leds[0], Led::from_rgb(255, 0, 0));
+        assert_eq!(rainbow.phase(), 90.0);
+
+        Effect::step(&mut rainbow, &mut strip, Duration::from_secs(3));
+        assert_eq!(rainbow.phase(), 0.0);
+    }
+
+    #[test]
+    fn test_meteor_shower() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut strip = Strip::new_mock(20);
+        let mut shower = MeteorShower::new(3, 4, 5.0..10.0, StdRng::seed_from_u64(2137));
+        assert_eq!(shower.meteors().len(), 3);
+        assert!(shower.meteors().iter().all(|meteor| meteor.position <= 0.0));
+
+        let before = shower.meteors().to_vec();
+        Effect::step(&mut shower, &mut strip, Duration::from_millis(500));
+        shower
+            .meteors()
+            .iter()
+            .zip(&before)
+            .for_each(|(after, before)| {
+                assert!((5.0..10.0).contains(&after.speed));
+                assert_eq!(after.position, before.position + before.speed * 0.5);
+            });
+
+        let mut lit_frames = 0;
+        for _ in 0..100 {
+            Effect::step(&mut shower, &mut strip, Duration::from_millis(100));
+            assert!(strip.count_lit() <= 3 * 4);
+            assert!(strip.iter().all(|led| led.w == 0));
+            if strip.count_lit() > 0 {
+                lit_frames += 1;
+            }
+            assert!(shower
+                .meteors()
+                .iter()
+                .all(|meteor| meteor.position < 20.0 + 4.0 + 1.0));
+        }
+        assert!(lit_frames > 0);
+    }
+}
@@ -0,0 +1,307 @@
+use crate::led::Led;
+use crate::segment::{clamp_range, Segment};
+use palette::{Gradient, LinSrgb};
+use rand::Rng;
+use std::{
+    ops::{Index, IndexMut, Range, RangeBounds},
+    slice,
+};
+
+/// Buffer of LED colors without any hardware attached, for rendering frames independently of the strip.
+/// Send it to the strip with `Strip::write_frame`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Frame {
+    pub leds: Vec<Led>,
+}
+
+impl Frame {
+    /// Create new frame with `amount_of_leds` turned off LEDs
+    pub fn new(amount_of_leds: usize) -> Self {
+        Self {
+            leds: vec![Led::new(); amount_of_leds],
+        }
+    }
+
+    /// Get the amount of LEDs in the frame
+    pub fn len(&self) -> usize {
+        self.leds.len()
+    }
+
+    /// Check if the frame has no LEDs
+    pub fn is_empty(&self) -> bool {
+        self.leds.is_empty()
+    }
+
+    /// Iterate over the LEDs of the frame
+    pub fn iter(&self) -> slice::Iter<'_, Led> {
+        self.leds.iter()
+    }
+
+    /// Iterate mutably over the LEDs of the frame
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, Led> {
+        self.leds.iter_mut()
+    }
+
+    /// Get a mutable view of LEDs in `range`. Parts of the range outside of the frame are ignored.
+    pub fn segment(&mut self, range: Range<usize>) -> Segment<'_> {
+        let range = clamp_range(range, self.leds.len());
+        Segment::new(&mut self.leds[range])
+    }
+
+    /// Set the color of all LEDs in the frame
+    pub fn fill(&mut self, led: Led) {
+        self.leds.fill(led);
+    }
+
+    /// Set the color of LEDs in specified range, for example `2..5`, `2..=4` or `..`.
+    /// Parts of the range outside of the frame are ignored.
+    pub fn fill_range(&mut self, range: impl RangeBounds<usize>, led: Led) {
+        Segment::new(&mut self.leds).fill_range(range, led);
+    }
+
+    /// Turn off all LEDs in the frame
+    pub fn clear(&mut self) {
+        self.leds.fill(Led::new());
+    }
+
+    /// Fill the frame with a gradient from `start` color on the first LED to `end` color on the last one.
+    /// See `Strip::fill_gradient` for details.
+    pub fn fill_gradient(&mut self, start: Led, end: Led) {
+        Segment::new(&mut self.leds).fill_gradient(start, end);
+    }
+
+    /// Fill the frame with a gradient defined by `(position, color)` stops, with positions in 0.0..=1.0 range sorted in ascending order.
+    /// See `Segment::fill_gradient_stops` for details.
+    pub fn fill_gradient_stops(&mut self, stops: &[(f32, Led)]) {
+        Segment::new(&mut self.leds).fill_gradient_stops(stops);
+    }
+
+    /// Fill the frame with a rainbow, see `Strip::fill_rainbow` for details
+    pub fn fill_rainbow(&mut self, start_hue: f32, hue_step: f32) {
+        Segment::new(&mut self.leds).fill_rainbow(start_hue, hue_step);
+    }
+
+    /// Scale HSV value (brightness) of every LED by `value`, see `Segment::set_value` for details
+    pub fn set_value(&mut self, value: f32) {
+        Segment::new(&mut self.leds).set_value(value);
+    }
+
+    /// Display `level` as a bar of lit LEDs, see `Segment::vu_meter` for details
+    pub fn vu_meter(&mut self, level: f32, low: Led, high: Led) {
+        Segment::new(&mut self.leds).vu_meter(level, low, high);
+    }
+
+    /// Dim all LEDs by subtracting `amount` from every channel, see `Segment::fade_to_black_by` for details
+    pub fn fade_to_black_by(&mut self, amount: u8) {
+        Segment::new(&mut self.leds).fade_to_black_by(amount);
+    }
+
+    /// Fill the frame with `color` when `on` is true, turn it off otherwise, see `Segment::blink` for details
+    pub fn blink(&mut self, color: Led, on: bool) {
+        Segment::new(&mut self.leds).blink(color, on);
+    }
+
+    /// Same as `blink`, but only for LEDs in specified range, see `Segment::blink_range` for details
+    pub fn blink_range(&mut self, range: impl RangeBounds<usize>, color: Led, on: bool) {
+        Segment::new(&mut self.leds).blink_range(range, color, on);
+    }
+
+    /// Fill the frame with `base` color scaled by sinusoidal brightness, see `Segment::apply_breathing` for details
+    pub fn apply_breathing(&mut self, base: Led, phase: f32) {
+        Segment::new(&mut self.leds).apply_breathing(base, phase);
+    }
+
+    /// Light every `spacing`-th LED with `color` and turn off the rest, see `Segment::theater_chase` for details
+    pub fn theater_chase(&mut self, color: Led, spacing: usize, offset: usize) {
+        Segment::new(&mut self.leds).theater_chase(color, spacing, offset);
+    }
+
+    /// Draw a comet at `head` index with a fading tail, see `Segment::comet` for details
+    pub fn comet(&mut self, head: usize, color: Led, tail_len: usize, decay: f32) {
+        Segment::new(&mut self.leds).comet(head, color, tail_len, decay);
+    }
+
+    /// Draw a glowing block at `center` index, fading out on both sides, see `Segment::larson_scanner` for details
+    pub fn larson_scanner(&mut self, center: usize, width: usize, color: Led) {
+        Segment::new(&mut self.leds).larson_scanner(center, width, color);
+    }
+
+    /// Light a random `density` fraction of LEDs with `color`, see `Segment::twinkle` for details
+    pub fn twinkle(&mut self, color: Led, density: f32, rng: &mut impl Rng) {
+        Segment::new(&mut self.leds).twinkle(color, density, rng);
+    }
+
+    /// Set all LEDs up to and including `position` to `color` and turn off the rest, see `Segment::color_wipe_step` for details
+    pub fn color_wipe_step(&mut self, color: Led, position: usize) {
+        Segment::new(&mut self.leds).color_wipe_step(color, position);
+    }
+
+    /// Set the frame LED colors to a `palette` gradient, see `Segment::set_gradient` for details
+    pub fn set_gradient(&mut self, gradient: Gradient<LinSrgb>) {
+        Segment::new(&mut self.leds).set_gradient(gradient);
+    }
+
+    /// Set the frame LED colors to a gamma-corrected `palette` gradient, see `Segment::set_gradient_gamma` for details
+    pub fn set_gradient_gamma(&mut self, gradient: Gradient<LinSrgb>, gamma: f32) {
+        Segment::new(&mut self.leds).set_gradient_gamma(gradient, gamma);
+    }
+
+    /// Fill the frame with a rainbow spanning `hue_span` degrees, see `Segment::animate_rainbow` for details
+    pub fn animate_rainbow(&mut self, phase: f32, hue_span: f32) {
+        Segment::new(&mut self.leds).animate_rainbow(phase, hue_span);
+    }
+
+    /// Fill the frame with colors sampled from looping `palette`, see `Segment::cycle_palette` for details
+    pub fn cycle_palette(&mut self, palette: &[Led], phase: f32) {
+        Segment::new(&mut self.leds).cycle_palette(palette, phase);
+    }
+
+    /// Fill the frame with a plasma effect, see `Segment::plasma_step` for details
+    pub fn plasma_step(&mut self, time: f32, scale: f32) {
+        Segment::new(&mut self.leds).plasma_step(time, scale);
+    }
+
+    /// Reflect the first half of the frame onto the second half, see `Strip::mirror` for details
+    pub fn mirror(&mut self) {
+        Segment::new(&mut self.leds).mirror();
+    }
+
+    /// Reverse the order of LEDs in the frame
+    pub fn reverse(&mut self) {
+        self.leds.reverse();
+    }
+
+    /// Move LEDs `count` positions towards the beginning of the frame, filling vacated LEDs with `fill`
+    pub fn scroll_left(&mut self, count: usize, fill: Led) {
+        Segment::new(&mut self.leds).scroll_left(count, fill);
+    }
+
+    /// Move LEDs `count` positions towards the end of the frame, filling vacated LEDs with `fill`
+    pub fn scroll_right(&mut self, count: usize, fill: Led) {
+        Segment::new(&mut self.leds).scroll_right(count, fill);
+    }
+
+    /// Rotate LEDs `count` positions towards the beginning of the frame, wrapping them around to the end
+    pub fn shift_left(&mut self, count: usize) {
+        self.leds.rotate_left(count);
+    }
+
+    /// Rotate LEDs `count` positions towards the end of the frame, wrapping them around to the beginning
+    pub fn shift_right(&mut self, count: usize) {
+        self.leds.rotate_right(count);
+    }
+}
+
+impl From<Vec<Led>> for Frame {
+    fn from(leds: Vec<Led>) -> Self {
+        Self { leds }
+    }
+}
+
+impl From<Frame> for Vec<Led> {
+    fn from(frame: Frame) -> Self {
+        frame.leds
+    }
+}
+
+impl Index<usize> for Frame {
+    type Output = Led;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.leds[index]
+    }
+}
+
+impl IndexMut<usize> for Frame {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.leds[index]
+    }
+}
+
+impl<'a> IntoIterator for &'a Frame {
+    type Item = &'a Led;
+    type IntoIter = slice::Iter<'a, Led>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Frame {
+    type Item = &'a mut Led;
+    type IntoIter = slice::IterMut<'a, Led>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_from_vec() {
+        let leds = vec![Led::from_rgb(1, 2, 3), Led::from_rgb(4, 5, 6)];
+        let frame = Frame::from(leds.clone());
+
+        assert_eq!(frame.len(), 2);
+        assert_eq!(frame[1], Led::from_rgb(4, 5, 6));
+        assert_eq!(Vec::from(frame), leds);
+        assert!(Frame::default().is_empty());
+    }
+
+    #[test]
+    fn test_frame_effects() {
+        let red = Led::from_rgb(255, 0, 0);
+        let blue = Led::from_rgb(0, 0, 255);
+        let mut frame = Frame::new(4);
+
+        frame.fill_range(..2, red);
+        frame.segment(2..10).fill(blue);
+        assert_eq!(frame.leds, [red, red, blue, blue]);
+
+        frame.shift_left(1);
+        assert_eq!(frame.leds, [red, blue, blue, red]);
+
+        frame.scroll_right(1, Led::new());
+        assert_eq!(frame.leds, [Led::new(), red, blue, blue]);
+
+        frame.reverse();
+        frame.mirror();
+        assert_eq!(frame.leds, [blue, blue, blue, blue]);
+
+        frame.fill_rainbow(0.0, 120.0);
+        assert_eq!(frame[1], Led::from_rgb(0, 255, 0));
+
+        frame.fill_gradient(Led::new(), Led::from_rgbw(255, 255, 255, 255));
+        assert_eq!(frame[3], Led::from_rgbw(255, 255, 255, 255));
+
+        frame.clear();
+        assert!(frame.iter().all(|led| *led == Led::new()));
+    }
+
+    #[test]
+    fn test_frame_effect_helpers() {
+        let red = Led::from_rgb(255, 0, 0);
+        let mut frame = Frame::new(5);
+
+        frame.color_wipe_step(red, 1);
+        assert_eq!(frame.leds, [red, red, Led::new(), Led::new(), Led::new()]);
+
+        frame.fade_to_black_by(55);
+        assert_eq!(frame[0], Led::from_rgb(200, 0, 0));
+
+        frame.theater_chase(red, 2, 0);
+        assert_eq!(frame.leds, [red, Led::new(), red, Led::new(), red]);
+
+        frame.comet(2, red, 1, 0.5);
+        assert_eq!(frame.leds[..3], [Led::new(), red * 0.5, red]);
+
+        frame.cycle_palette(&[red, Led::new()], 0.0);
+        assert_eq!(frame[0], red);
+
+        frame.blink(red, false);
+        assert!(frame.iter().all(|led| led.is_off()));
+    }
+}
@@ -1,15 +1,155 @@
-use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+use std::{
+    cmp::Ordering,
+    error::Error,
+    fmt,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
+};
 
-use bitvec::prelude::*;
-use palette::{rgb::Rgb, FromColor, Hsl, Hsv, Srgb};
+use crate::colors;
+use palette::{rgb::Rgb, Clamp, ColorDifference, FromColor, Hsl, Hsv, Lab, Srgb};
 
 /// High bit (logical 1) representation for SPI
 const BIT_HIGH: u8 = 0b11110000;
 /// Low bit (logical 0) representation for SPI
 const BIT_LOW: u8 = 0b11000000;
+/// SPI representation of every possible channel value, with each bit (MSB first) expanded into a single byte
+const SPI_BYTES_LUT: [[u8; 8]; 256] = spi_bytes_lut();
+
+// Build the lookup table of SPI representations for all channel values at compile time
+const fn spi_bytes_lut() -> [[u8; 8]; 256] {
+    let mut lut = [[BIT_LOW; 8]; 256];
+    let mut value = 0;
+    while value < 256 {
+        let mut bit = 0;
+        while bit < 8 {
+            if value & (0x80 >> bit) != 0 {
+                lut[value][bit] = BIT_HIGH;
+            }
+            bit += 1;
+        }
+        value += 1;
+    }
+    lut
+}
 
-/// Structure representing a single RGBW LED
+/// Error returned when a color cannot be parsed from a string
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseColorError {
+    /// Hex color has wrong amount of digits, only 6 (RGB) and 8 (RGBW) are supported
+    InvalidLength(usize),
+    /// Hex color contains a character that's not a hex digit
+    InvalidDigit(char),
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseColorError::InvalidLength(length) => write!(
+                f,
+                "invalid hex color length: expected 6 or 8 digits, got {}",
+                length
+            ),
+            ParseColorError::InvalidDigit(digit) => {
+                write!(f, "invalid hex color digit: '{}'", digit)
+            }
+        }
+    }
+}
+
+impl Error for ParseColorError {}
+
+/// Order in which color channels are sent to the LED.
+/// SK6812RGBW uses GRBW, but some clones and variants expect different ordering.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorOrder {
+    #[default]
+    Grbw,
+    Rgbw,
+    Rbgw,
+    Gbrw,
+    Brgw,
+    Bgrw,
+}
+
+impl ColorOrder {
+    /// Arrange LED channels in this order
+    pub fn arrange(&self, led: &Led) -> [u8; 4] {
+        match self {
+            ColorOrder::Grbw => [led.g, led.r, led.b, led.w],
+            ColorOrder::Rgbw => [led.r, led.g, led.b, led.w],
+            ColorOrder::Rbgw => [led.r, led.b, led.g, led.w],
+            ColorOrder::Gbrw => [led.g, led.b, led.r, led.w],
+            ColorOrder::Brgw => [led.b, led.r, led.g, led.w],
+            ColorOrder::Bgrw => [led.b, led.g, led.r, led.w],
+        }
+    }
+}
+
+/// Single color channel of the LED, see `Led::swizzle`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Channel {
+    R,
+    G,
+    B,
+    W,
+}
+
+impl Channel {
+    /// Get the value of this channel of `led`
+    pub fn get(&self, led: &Led) -> u8 {
+        match self {
+            Channel::R => led.r,
+            Channel::G => led.g,
+            Channel::B => led.b,
+            Channel::W => led.w,
+        }
+    }
+}
+
+/// Format of pixel data sent to the LED
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PixelFormat {
+    /// Four channels with white, used by SK6812RGBW
+    #[default]
+    Rgbw,
+    /// Three channels without white, used by WS2812 and SK6812RGB. White channel of `Led` is ignored.
+    Rgb,
+}
+
+impl PixelFormat {
+    /// Amount of color channels sent for a single LED
+    pub fn channels(&self) -> usize {
+        match self {
+            PixelFormat::Rgbw => 4,
+            PixelFormat::Rgb => 3,
+        }
+    }
+
+    /// Amount of SPI bytes sent for a single LED
+    pub fn raw_bytes_per_led(&self) -> usize {
+        self.channels() * 8
+    }
+}
+
+/// Mode used for blending two colors together with `Led::blend`.
+/// Every mode operates on each channel (including white) separately.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlendMode {
+    /// Use the other color, ignoring the base one
+    Normal,
+    /// Multiply channels as fractions of 255, which darkens the color. White is identity.
+    Multiply,
+    /// Inverse of multiplying inverted channels, which brightens the color. Black is identity.
+    Screen,
+    /// Add channels, saturating at 255
+    Add,
+    /// Take the brighter value of each channel
+    Lighten,
+}
+
+/// Structure representing a single RGBW LED
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Led {
     // Red color
     pub r: u8,
@@ -27,7 +167,7 @@ impl Led {
     }
 
     // Create LED from RGB 8-bit colors. White is set to 0.
-    pub fn from_rgb(red: u8, green: u8, blue: u8) -> Self {
+    pub const fn from_rgb(red: u8, green: u8, blue: u8) -> Self {
         Self {
             r: red,
             g: green,
@@ -37,7 +177,7 @@ impl Led {
     }
 
     // Create LED from RGBW 8-bit colors.
-    pub fn from_rgbw(red: u8, green: u8, blue: u8, white: u8) -> Self {
+    pub const fn from_rgbw(red: u8, green: u8, blue: u8, white: u8) -> Self {
         Self {
             r: red,
             g: green,
@@ -46,6 +186,48 @@ impl Led {
         }
     }
 
+    /// Get the color for common CSS color name (case-insensitive), like `"red"`, `"navy"` or `"gold"`.
+    /// White uses the white channel. See `colors` module for named color constants.
+    pub fn from_name(name: &str) -> Option<Self> {
+        colors::from_name(name)
+    }
+
+    /// Get a copy of the LED with red channel set to `r`
+    pub fn with_r(self, r: u8) -> Self {
+        Self { r, ..self }
+    }
+
+    /// Get a copy of the LED with green channel set to `g`
+    pub fn with_g(self, g: u8) -> Self {
+        Self { g, ..self }
+    }
+
+    /// Get a copy of the LED with blue channel set to `b`
+    pub fn with_b(self, b: u8) -> Self {
+        Self { b, ..self }
+    }
+
+    /// Get a copy of the LED with white channel set to `w`
+    pub fn with_w(self, w: u8) -> Self {
+        Self { w, ..self }
+    }
+
+    /// Swap red and green channels
+    pub fn swap_rg(self) -> Self {
+        self.swizzle([Channel::G, Channel::R, Channel::B, Channel::W])
+    }
+
+    /// Swap red and blue channels
+    pub fn swap_rb(self) -> Self {
+        self.swizzle([Channel::B, Channel::G, Channel::R, Channel::W])
+    }
+
+    /// Rearrange channels - red, green, blue and white of the result are taken from the channels listed in `order`.
+    /// Channels can repeat, so `[Channel::R; 4]` copies red to every channel.
+    pub fn swizzle(self, order: [Channel; 4]) -> Self {
+        Led::from_rgbw_array(order.map(|channel| channel.get(&self)))
+    }
+
     // Create LED from 3-element array of 8-bit values. The order of colors is red, green, blue. White is set to 0.
     pub fn from_rgb_array(data: [u8; 3]) -> Self {
         data.into()
@@ -56,6 +238,27 @@ impl Led {
         data.into()
     }
 
+    /// Create LED from packed `0xRRGGBBWW` value
+    pub fn from_u32(value: u32) -> Self {
+        value.into()
+    }
+
+    /// Create LED from packed `0xRRGGBB` value. Highest byte is ignored, white is set to 0.
+    pub fn from_rgb_u32(value: u32) -> Self {
+        let [_, r, g, b] = value.to_be_bytes();
+        Self::from_rgb(r, g, b)
+    }
+
+    /// Convert the LED into packed `0xRRGGBBWW` value
+    pub fn to_u32(self) -> u32 {
+        self.into()
+    }
+
+    /// Convert the LED into packed `0xRRGGBB` value, ignoring white
+    pub fn to_rgb_u32(self) -> u32 {
+        u32::from_be_bytes([0, self.r, self.g, self.b])
+    }
+
     // Convert the LED into 4-element RGBW 8-bit array
     pub fn into_rgbw_array(self) -> [u8; 4] {
         self.into()
@@ -66,17 +269,340 @@ impl Led {
         self.into()
     }
 
+    /// Create LED from hex color string, with or without leading `#`.
+    /// Accepts 6-digit RGB (`#FF8800`, white is set to 0) and 8-digit RGBW (`#FF8800CC`) forms.
+    pub fn from_hex(hex: &str) -> Result<Self, ParseColorError> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+
+        if let Some(invalid_digit) = digits.chars().find(|c| !c.is_ascii_hexdigit()) {
+            return Err(ParseColorError::InvalidDigit(invalid_digit));
+        }
+
+        if digits.len() != 6 && digits.len() != 8 {
+            return Err(ParseColorError::InvalidLength(digits.len()));
+        }
+
+        let mut channels = [0u8; 4];
+        channels
+            .iter_mut()
+            .zip(digits.as_bytes().chunks(2))
+            .for_each(|(channel, pair)| {
+                // Digits are already validated, so this can't fail
+                *channel = u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap();
+            });
+
+        Ok(channels.into())
+    }
+
+    /// Convert the LED into 8-digit RGBW hex color string, in `#RRGGBBWW` format
+    pub fn to_hex_string(&self) -> String {
+        format!("#{:02X}{:02X}{:02X}{:02X}", self.r, self.g, self.b, self.w)
+    }
+
+    /// Linearly interpolate between this color and `other`, channel by channel.
+    /// `t` equal to 0.0 returns this color, 1.0 returns `other`. `t` is clamped to 0.0..=1.0 range.
+    pub fn lerp(self, other: Led, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let lerp_channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+
+        Self {
+            r: lerp_channel(self.r, other.r),
+            g: lerp_channel(self.g, other.g),
+            b: lerp_channel(self.b, other.b),
+            w: lerp_channel(self.w, other.w),
+        }
+    }
+
+    /// Move the common white part of RGB channels into the white channel.
+    /// The minimum of red, green and blue is subtracted from them and added to white (saturating),
+    /// which gives cleaner whites and lower power consumption on RGBW LEDs.
+    pub fn auto_white(self) -> Self {
+        let white = self.r.min(self.g).min(self.b);
+
+        Self {
+            r: self.r - white,
+            g: self.g - white,
+            b: self.b - white,
+            w: self.w.saturating_add(white),
+        }
+    }
+
+    /// Add the common white part of RGB channels, scaled by `factor`, to the white channel (saturating),
+    /// without removing it from RGB channels. Gives brighter and cooler whites than `auto_white`, at the cost of power.
+    pub fn boost_white(self, factor: f32) -> Self {
+        let white = self.r.min(self.g).min(self.b) as f32 * factor.max(0.0);
+
+        Self {
+            w: (self.w as f32 + white).round().min(u8::MAX as f32) as u8,
+            ..self
+        }
+    }
+
+    /// Create LED approximating the color of black body radiation at `temperature` (in Kelvins),
+    /// clamped to 1000K..=12000K range. 2700K is a warm white, 6500K is neutral daylight.
+    /// The common white part of the color is moved to the white channel (see `auto_white`).
+    pub fn from_kelvin(temperature: u16) -> Self {
+        // Tanner Helland's approximation of black body color, works on hundreds of Kelvins
+        let t = temperature.clamp(1000, 12000) as f32 / 100.0;
+        let channel = |value: f32| value.round().clamp(0.0, u8::MAX as f32) as u8;
+
+        let (red, green) = if t <= 66.0 {
+            (255.0, 99.4708 * t.ln() - 161.11957)
+        } else {
+            (
+                329.69873 * (t - 60.0).powf(-0.13320476),
+                288.12216 * (t - 60.0).powf(-0.07551485),
+            )
+        };
+        let blue = match t {
+            t if t >= 66.0 => 255.0,
+            t if t <= 19.0 => 0.0,
+            t => 138.51773 * (t - 10.0).ln() - 305.0448,
+        };
+
+        Led::from_rgb(channel(red), channel(green), channel(blue)).auto_white()
+    }
+
+    /// Check if all channels of the LED are turned off
+    pub fn is_off(&self) -> bool {
+        *self == Led::new()
+    }
+
+    /// Get perceived brightness of the LED in 0.0..=1.0 range.
+    /// RGB channels are weighted using Rec. 709 coefficients, white channel is added with full weight.
+    pub fn luminance(&self) -> f32 {
+        let luminance = 0.2126 * self.r as f32
+            + 0.7152 * self.g as f32
+            + 0.0722 * self.b as f32
+            + self.w as f32;
+        (luminance / u8::MAX as f32).min(1.0)
+    }
+
+    /// Convert RGB channels to gray of the same perceived brightness (Rec. 709), keeping white channel as-is
+    pub fn grayscale(self) -> Self {
+        let gray = self.rgb_luminance();
+        Led::from_rgbw(gray, gray, gray, self.w)
+    }
+
+    /// Convert RGB channels to gray of the same perceived brightness (Rec. 709) and move it to the white channel.
+    /// RGB channels are turned off, gray value is added to existing white (saturating).
+    pub fn grayscale_to_white(self) -> Self {
+        Led::from_rgbw(0, 0, 0, self.w.saturating_add(self.rgb_luminance()))
+    }
+
+    /// Scale the color down proportionally so that no channel exceeds `max`, preserving the hue.
+    /// Colors with all channels at or below `max` are returned unchanged.
+    pub fn clamp_brightness(self, max: u8) -> Self {
+        let brightest = self.r.max(self.g).max(self.b).max(self.w);
+        if brightest <= max {
+            return self;
+        }
+
+        let scale = max as f32 / brightest as f32;
+        let scale_channel = |value: u8| (value as f32 * scale).round() as u8;
+        Led::from_rgbw(
+            scale_channel(self.r),
+            scale_channel(self.g),
+            scale_channel(self.b),
+            scale_channel(self.w),
+        )
+    }
+
+    /// Add colors channel-wise like mixing light. If any channel exceeds 255, all channels are scaled down proportionally,
+    /// preserving the hue instead of clipping it like `saturating_add` does.
+    pub fn mix_additive_clamped(self, other: Led) -> Self {
+        let sums = [
+            self.r as u16 + other.r as u16,
+            self.g as u16 + other.g as u16,
+            self.b as u16 + other.b as u16,
+            self.w as u16 + other.w as u16,
+        ];
+        let max = sums.into_iter().max().unwrap_or(0);
+        let scale = if max > u8::MAX as u16 {
+            u8::MAX as f32 / max as f32
+        } else {
+            1.0
+        };
+
+        Led::from_rgbw_array(sums.map(|sum| (sum as f32 * scale).round() as u8))
+    }
+
+    /// Scale all channels by `alpha` (0.0..=1.0), for compositing with externally tracked alpha.
+    /// Channels are rounded and clamped.
+    pub fn premultiply(self, alpha: f32) -> Self {
+        (self.to_f32() * alpha.clamp(0.0, 1.0)).to_led()
+    }
+
+    /// Inverse of `premultiply`, divides all channels by `alpha`. Returns black when `alpha` is zero.
+    /// Channels are rounded and clamped.
+    pub fn unpremultiply(self, alpha: f32) -> Self {
+        if alpha > 0.0 {
+            (self.to_f32() / alpha.min(1.0)).to_led()
+        } else {
+            Led::new()
+        }
+    }
+
+    /// Add colors in linear light space instead of gamma-encoded sRGB, clamping the result.
+    /// This mixes overlapping light sources (like particles) physically correctly, unlike the plain `+` operator.
+    pub fn add_linear(self, other: Led) -> Self {
+        let to_linear = |led: Led| {
+            (
+                Srgb::new(led.r, led.g, led.b)
+                    .into_format::<f32>()
+                    .into_linear(),
+                Srgb::new(led.w, led.w, led.w)
+                    .into_format::<f32>()
+                    .into_linear(),
+            )
+        };
+        let (self_rgb, self_white) = to_linear(self);
+        let (other_rgb, other_white) = to_linear(other);
+
+        let rgb: Srgb<u8> = Srgb::from_linear((self_rgb + other_rgb).clamp()).into_format();
+        let white: Srgb<u8> = Srgb::from_linear((self_white + other_white).clamp()).into_format();
+        Led::from_rgbw(rgb.red, rgb.green, rgb.blue, white.red)
+    }
+
+    /// Get Euclidean distance between two colors across all four channels, normalized to 0.0..=1.0 range
+    pub fn distance(&self, other: &Led) -> f32 {
+        let sum: f32 = <[u8; 4]>::from(*self)
+            .iter()
+            .zip(<[u8; 4]>::from(*other).iter())
+            .map(|(&a, &b)| (a as f32 - b as f32).powi(2))
+            .sum();
+        (sum / (4.0 * (u8::MAX as f32).powi(2))).sqrt()
+    }
+
+    /// Get perceptual (CIEDE2000) distance between two colors, computed in CIE L*a*b* color space.
+    /// White channel is mixed into RGB before conversion. 0.0 means identical colors, difference of ~2.3 is barely noticeable.
+    pub fn perceptual_distance(&self, other: &Led) -> f32 {
+        let to_lab = |led: &Led| {
+            let white = Led::from_rgb(led.w, led.w, led.w);
+            Lab::from_color(Rgb::from(Led::from_rgb(led.r, led.g, led.b) + white))
+        };
+        to_lab(self).get_color_difference(&to_lab(other))
+    }
+
+    /// Add colors channel-wise, saturating at 255. Same as `+` operator.
+    pub fn saturating_add(self, rhs: Led) -> Self {
+        self.combine(rhs, u8::saturating_add)
+    }
+
+    /// Add colors channel-wise, wrapping around on overflow (so 255 + 1 = 0)
+    pub fn wrapping_add(self, rhs: Led) -> Self {
+        self.combine(rhs, u8::wrapping_add)
+    }
+
+    /// Add colors channel-wise, returning `None` if any of the channels overflows
+    pub fn checked_add(self, rhs: Led) -> Option<Self> {
+        Some(Self {
+            r: self.r.checked_add(rhs.r)?,
+            g: self.g.checked_add(rhs.g)?,
+            b: self.b.checked_add(rhs.b)?,
+            w: self.w.checked_add(rhs.w)?,
+        })
+    }
+
+    /// Decrease saturation of the color by `amount` in HSV space. Saturation is clamped to 0.0..=1.0, white channel is preserved.
+    pub fn desaturate(self, amount: f32) -> Self {
+        self.saturate(-amount)
+    }
+
+    /// Increase saturation of the color by `amount` in HSV space. Saturation is clamped to 0.0..=1.0, white channel is preserved.
+    pub fn saturate(self, amount: f32) -> Self {
+        self.with_hsv(|hsv| hsv.saturation = (hsv.saturation + amount).clamp(0.0, 1.0))
+    }
+
+    /// Transform the RGB part of the color in HSV space, keeping the white channel intact.
+    /// Plain `Hsv` conversions drop the white channel, so use this for hue/saturation/value effects on RGBW colors.
+    pub fn with_hsv<F: Fn(&mut Hsv)>(self, f: F) -> Self {
+        let mut hsv: Hsv = self.into();
+        f(&mut hsv);
+
+        Self {
+            w: self.w,
+            ..hsv.into()
+        }
+    }
+
+    /// Transform the RGB part of the color in HSL space, keeping the white channel intact.
+    pub fn with_hsl<F: Fn(&mut Hsl)>(self, f: F) -> Self {
+        let mut hsl: Hsl = self.into();
+        f(&mut hsl);
+
+        Self {
+            w: self.w,
+            ..hsl.into()
+        }
+    }
+
+    /// Blend `other` color on top of this one, using specified blend mode
+    pub fn blend(self, other: Led, mode: BlendMode) -> Self {
+        match mode {
+            BlendMode::Normal => other,
+            BlendMode::Multiply => self * other,
+            BlendMode::Screen => self.combine(other, |a, b| {
+                u8::MAX - ((u8::MAX - a) as u16 * (u8::MAX - b) as u16 / u8::MAX as u16) as u8
+            }),
+            BlendMode::Add => self + other,
+            BlendMode::Lighten => self.combine(other, u8::max),
+        }
+    }
+
     /// Converts the instance of this struct to SK6812-compatible byte array for SPI.
     /// Don't use in your own code, unless you know what you're doing.
     pub fn to_raw_led_bytes(&self) -> Vec<u8> {
-        [self.g, self.r, self.b, self.w]
-            .view_bits::<Msb0>()
-            .iter()
-            .map(|bit| match *bit {
-                true => BIT_HIGH,
-                false => BIT_LOW,
-            })
-            .collect()
+        self.to_raw_led_bytes_with_order(ColorOrder::Grbw)
+    }
+
+    /// Converts the instance of this struct to byte array for SPI, with channels sent in specified order.
+    /// Don't use in your own code, unless you know what you're doing.
+    pub fn to_raw_led_bytes_with_order(&self, order: ColorOrder) -> Vec<u8> {
+        self.raw_led_bytes(order).to_vec()
+    }
+
+    // Encode the LED into SPI bytes without allocating, used internally by `Strip`
+    pub(crate) fn raw_led_bytes(&self, order: ColorOrder) -> [u8; 32] {
+        let mut bytes = [0; 32];
+        bytes
+            .chunks_exact_mut(8)
+            .zip(order.arrange(self))
+            .for_each(|(channel_bytes, channel)| {
+                channel_bytes.copy_from_slice(&SPI_BYTES_LUT[channel as usize])
+            });
+        bytes
+    }
+}
+
+impl Led {
+    // Rec. 709 luminance of RGB channels only, as 0..=255 value
+    fn rgb_luminance(&self) -> u8 {
+        (0.2126 * self.r as f32 + 0.7152 * self.g as f32 + 0.0722 * self.b as f32).round() as u8
+    }
+
+    // Combine two LEDs channel-wise using provided function
+    fn combine(self, other: Led, f: impl Fn(u8, u8) -> u8) -> Self {
+        Self {
+            r: f(self.r, other.r),
+            g: f(self.g, other.g),
+            b: f(self.b, other.b),
+            w: f(self.w, other.w),
+        }
+    }
+}
+
+/// Orders LEDs by their packed `0xRRGGBBWW` value (see `to_u32`), so red is the most significant channel
+/// and white is the least significant one.
+impl Ord for Led {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.to_u32().cmp(&other.to_u32())
+    }
+}
+
+impl PartialOrd for Led {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
@@ -84,12 +610,7 @@ impl Add for Led {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        Led::from_rgbw_array([
-            self.r.saturating_add(rhs.r),
-            self.g.saturating_add(rhs.g),
-            self.b.saturating_add(rhs.b),
-            self.w.saturating_add(rhs.w),
-        ])
+        self.saturating_add(rhs)
     }
 }
 
@@ -106,15 +627,19 @@ impl Sub for Led {
     }
 }
 
+/// Multiplies the colors channel-wise, treating every channel as a fraction of 255.
+/// Multiplying by white (255) leaves the color unchanged, multiplying by black (0) gives black.
 impl Mul for Led {
     type Output = Self;
 
     fn mul(self, rhs: Self) -> Self::Output {
+        let mul_channel = |a: u8, b: u8| (a as u16 * b as u16 / u8::MAX as u16) as u8;
+
         Led::from_rgbw_array([
-            self.r.saturating_mul(rhs.r),
-            self.g.saturating_mul(rhs.g),
-            self.b.saturating_mul(rhs.b),
-            self.w.saturating_mul(rhs.w),
+            mul_channel(self.r, rhs.r),
+            mul_channel(self.g, rhs.g),
+            mul_channel(self.b, rhs.b),
+            mul_channel(self.w, rhs.w),
         ])
     }
 }
@@ -152,7 +677,12 @@ impl Mul<u8> for Led {
     type Output = Self;
 
     fn mul(self, rhs: u8) -> Self::Output {
-        self * Led::from_rgbw(rhs, rhs, rhs, rhs)
+        Led::from_rgbw_array([
+            self.r.saturating_mul(rhs),
+            self.g.saturating_mul(rhs),
+            self.b.saturating_mul(rhs),
+            self.w.saturating_mul(rhs),
+        ])
     }
 }
 
@@ -182,29 +712,21 @@ impl Sub<f32> for Led {
     }
 }
 
+/// Scales every channel, rounding the result to nearest integer and clamping it to 0..=255 range
 impl Mul<f32> for Led {
     type Output = Self;
 
     fn mul(self, rhs: f32) -> Self::Output {
-        Led {
-            r: (self.r as f32 * rhs) as u8,
-            g: (self.g as f32 * rhs) as u8,
-            b: (self.b as f32 * rhs) as u8,
-            w: (self.w as f32 * rhs) as u8,
-        }
+        (self.to_f32() * rhs).to_led()
     }
 }
 
+/// Divides every channel, rounding the result to nearest integer and clamping it to 0..=255 range
 impl Div<f32> for Led {
     type Output = Self;
 
     fn div(self, rhs: f32) -> Self::Output {
-        Led {
-            r: (self.r as f32 / rhs) as u8,
-            g: (self.g as f32 / rhs) as u8,
-            b: (self.b as f32 / rhs) as u8,
-            w: (self.w as f32 / rhs) as u8,
-        }
+        (self.to_f32() / rhs).to_led()
     }
 }
 
@@ -314,6 +836,18 @@ impl From<[u8; 4]> for Led {
     }
 }
 
+impl From<u32> for Led {
+    fn from(value: u32) -> Self {
+        value.to_be_bytes().into()
+    }
+}
+
+impl From<Led> for u32 {
+    fn from(led: Led) -> Self {
+        u32::from_be_bytes(led.into())
+    }
+}
+
 impl From<Led> for Rgb {
     fn from(led: Led) -> Self {
         Rgb::new(
@@ -361,6 +895,206 @@ impl From<Hsl> for Led {
     }
 }
 
+/// Maps `r`, `g`, `b` channels directly and white channel to `a` (`White`) component of `smart-leds` color
+#[cfg(feature = "smart-leds")]
+impl From<smart_leds::RGBW<u8>> for Led {
+    fn from(color: smart_leds::RGBW<u8>) -> Self {
+        Led::from_rgbw(color.r, color.g, color.b, color.a.0)
+    }
+}
+
+/// Maps `r`, `g`, `b` channels directly and white channel to `a` (`White`) component of `smart-leds` color
+#[cfg(feature = "smart-leds")]
+impl From<Led> for smart_leds::RGBW<u8> {
+    fn from(led: Led) -> Self {
+        smart_leds::RGBW {
+            r: led.r,
+            g: led.g,
+            b: led.b,
+            a: smart_leds::White(led.w),
+        }
+    }
+}
+
+/// LED color with floating-point channels, for accumulating effects without rounding error of `u8` arithmetic.
+/// Channels use the same 0.0..=255.0 range as `Led`, values outside of it are allowed and clamped on conversion.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LedF32 {
+    // Red color
+    pub r: f32,
+    // Green color
+    pub g: f32,
+    // Blue color
+    pub b: f32,
+    // White color
+    pub w: f32,
+}
+
+impl LedF32 {
+    /// Create new floating-point LED color
+    pub fn new(r: f32, g: f32, b: f32, w: f32) -> Self {
+        Self { r, g, b, w }
+    }
+
+    /// Convert to `Led`, rounding and clamping channels to 0..=255 range
+    pub fn to_led(self) -> Led {
+        let quantize = |value: f32| value.round().clamp(0.0, u8::MAX as f32) as u8;
+        Led::from_rgbw(
+            quantize(self.r),
+            quantize(self.g),
+            quantize(self.b),
+            quantize(self.w),
+        )
+    }
+
+    // Apply provided function to every channel
+    fn map(self, f: impl Fn(f32) -> f32) -> Self {
+        Self::new(f(self.r), f(self.g), f(self.b), f(self.w))
+    }
+
+    // Combine two colors channel-wise using provided function
+    fn combine(self, other: LedF32, f: impl Fn(f32, f32) -> f32) -> Self {
+        Self::new(
+            f(self.r, other.r),
+            f(self.g, other.g),
+            f(self.b, other.b),
+            f(self.w, other.w),
+        )
+    }
+}
+
+impl Led {
+    /// Convert to floating-point color, see `LedF32`
+    pub fn to_f32(self) -> LedF32 {
+        LedF32::new(self.r as f32, self.g as f32, self.b as f32, self.w as f32)
+    }
+}
+
+impl From<Led> for LedF32 {
+    fn from(led: Led) -> Self {
+        led.to_f32()
+    }
+}
+
+impl From<LedF32> for Led {
+    fn from(led: LedF32) -> Self {
+        led.to_led()
+    }
+}
+
+impl Add for LedF32 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.combine(rhs, |a, b| a + b)
+    }
+}
+
+impl Sub for LedF32 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.combine(rhs, |a, b| a - b)
+    }
+}
+
+/// Multiplies the colors channel-wise, treating every channel as a fraction of 255, same as for `Led`.
+impl Mul for LedF32 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.combine(rhs, |a, b| a * b / u8::MAX as f32)
+    }
+}
+
+impl Div for LedF32 {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.combine(rhs, |a, b| a / b)
+    }
+}
+
+impl Add<f32> for LedF32 {
+    type Output = Self;
+
+    fn add(self, rhs: f32) -> Self::Output {
+        self.map(|value| value + rhs)
+    }
+}
+
+impl Sub<f32> for LedF32 {
+    type Output = Self;
+
+    fn sub(self, rhs: f32) -> Self::Output {
+        self.map(|value| value - rhs)
+    }
+}
+
+impl Mul<f32> for LedF32 {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        self.map(|value| value * rhs)
+    }
+}
+
+impl Div<f32> for LedF32 {
+    type Output = Self;
+
+    fn div(self, rhs: f32) -> Self::Output {
+        self.map(|value| value / rhs)
+    }
+}
+
+impl AddAssign for LedF32 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for LedF32 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for LedF32 {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign for LedF32 {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl AddAssign<f32> for LedF32 {
+    fn add_assign(&mut self, rhs: f32) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign<f32> for LedF32 {
+    fn sub_assign(&mut self, rhs: f32) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign<f32> for LedF32 {
+    fn mul_assign(&mut self, rhs: f32) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign<f32> for LedF32 {
+    fn div_assign(&mut self, rhs: f32) {
+        *self = *self / rhs;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -389,6 +1123,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_led_to_byte_vec_conversion_with_order() {
+        let led = Led::from_rgbw(0xAA, 0x00, 0xFF, 0x33);
+
+        assert_eq!(
+            led.to_raw_led_bytes_with_order(ColorOrder::Grbw),
+            led.to_raw_led_bytes()
+        );
+        assert_eq!(
+            led.to_raw_led_bytes_with_order(ColorOrder::Rgbw),
+            Led::from_rgbw(0x00, 0xAA, 0xFF, 0x33).to_raw_led_bytes()
+        );
+        assert_eq!(
+            led.to_raw_led_bytes_with_order(ColorOrder::Bgrw),
+            Led::from_rgbw(0x00, 0xFF, 0xAA, 0x33).to_raw_led_bytes()
+        );
+    }
+
     #[test]
     fn test_led_to_byte_array_conversion() {
         let led = Led {
@@ -485,14 +1237,14 @@ mod tests {
     #[test]
     fn test_led_mul() {
         let led_a = Led::from_rgbw(10, 20, 30, 40);
-        let led_b = Led::from_rgbw(3, 2, 1, 2);
+        let led_b = Led::from_rgbw(255, 128, 51, 0);
 
         let led_multiplied = led_a * led_b;
-        assert_eq!(led_multiplied, Led::from_rgbw(30, 40, 30, 80));
+        assert_eq!(led_multiplied, Led::from_rgbw(10, 10, 6, 0));
 
         let mut led_c = led_a;
         led_c *= led_b;
-        assert_eq!(led_c, Led::from_rgbw(30, 40, 30, 80));
+        assert_eq!(led_c, Led::from_rgbw(10, 10, 6, 0));
 
         let mut led_d = led_a;
         led_d *= 2;
@@ -503,6 +1255,18 @@ mod tests {
         assert_eq!(led_e, Led::from_rgbw(5, 10, 15, 20));
     }
 
+    #[test]
+    fn test_led_mul_identity_and_zero() {
+        let led = Led::from_rgbw(10, 120, 200, 255);
+        let white = Led::from_rgbw(255, 255, 255, 255);
+        let black = Led::new();
+
+        assert_eq!(led * white, led);
+        assert_eq!(white * led, led);
+        assert_eq!(led * black, black);
+        assert_eq!(black * led, black);
+    }
+
     #[test]
     fn test_led_div() {
         let led_a = Led::from_rgbw(10, 20, 30, 40);
@@ -523,4 +1287,370 @@ mod tests {
         led_e /= 0.5;
         assert_eq!(led_e, Led::from_rgbw(20, 40, 60, 80));
     }
+
+    #[test]
+    fn test_led_lerp() {
+        let from = Led::from_rgbw(0, 0, 0, 0);
+        let to = Led::from_rgbw(100, 200, 50, 10);
+
+        assert_eq!(from.lerp(to, 0.0), from);
+        assert_eq!(from.lerp(to, 1.0), to);
+        assert_eq!(from.lerp(to, 0.5), Led::from_rgbw(50, 100, 25, 5));
+        assert_eq!(from.lerp(to, -1.0), from);
+        assert_eq!(from.lerp(to, 2.0), to);
+    }
+
+    #[test]
+    fn test_led_hex_conversion() {
+        let led_rgb = Led::from_hex("#FF8800").unwrap();
+        assert_eq!(led_rgb, Led::from_rgb(0xFF, 0x88, 0x00));
+        assert_eq!(led_rgb.to_hex_string(), "#FF880000");
+
+        let led_rgbw = Led::from_hex("ff8800cc").unwrap();
+        assert_eq!(led_rgbw, Led::from_rgbw(0xFF, 0x88, 0x00, 0xCC));
+        assert_eq!(led_rgbw.to_hex_string(), "#FF8800CC");
+        assert_eq!(Led::from_hex(&led_rgbw.to_hex_string()), Ok(led_rgbw));
+    }
+
+    #[test]
+    fn test_led_hex_parsing_errors() {
+        assert_eq!(
+            Led::from_hex("#FF88"),
+            Err(ParseColorError::InvalidLength(4))
+        );
+        assert_eq!(
+            Led::from_hex("#FF8800C"),
+            Err(ParseColorError::InvalidLength(7))
+        );
+        assert_eq!(
+            Led::from_hex("#FF88G0"),
+            Err(ParseColorError::InvalidDigit('G'))
+        );
+        assert_eq!(Led::from_hex(""), Err(ParseColorError::InvalidLength(0)));
+    }
+
+    #[test]
+    fn test_led_blend() {
+        let led = Led::from_rgbw(10, 120, 200, 255);
+        let white = Led::from_rgbw(255, 255, 255, 255);
+        let other = Led::from_rgbw(50, 100, 250, 0);
+
+        assert_eq!(led.blend(other, BlendMode::Normal), other);
+        assert_eq!(led.blend(white, BlendMode::Screen), white);
+        assert_eq!(white.blend(led, BlendMode::Screen), white);
+        assert_eq!(led.blend(white, BlendMode::Multiply), led);
+        assert_eq!(
+            led.blend(other, BlendMode::Add),
+            Led::from_rgbw(60, 220, 255, 255)
+        );
+        assert_eq!(
+            led.blend(other, BlendMode::Lighten),
+            Led::from_rgbw(50, 120, 250, 255)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_led_serde_round_trip() {
+        let led = Led::from_rgbw(10, 20, 30, 40);
+
+        let json = serde_json::to_string(&led).unwrap();
+        assert_eq!(json, r#"{"r":10,"g":20,"b":30,"w":40}"#);
+        assert_eq!(serde_json::from_str::<Led>(&json).unwrap(), led);
+    }
+
+    #[test]
+    fn test_led_auto_white() {
+        assert_eq!(
+            Led::from_rgb(100, 100, 100).auto_white(),
+            Led::from_rgbw(0, 0, 0, 100)
+        );
+        assert_eq!(
+            Led::from_rgb(120, 80, 40).auto_white(),
+            Led::from_rgbw(80, 40, 0, 40)
+        );
+    }
+
+    #[test]
+    fn test_led_luminance() {
+        let green = Led::from_rgb(0, 255, 0);
+        let blue = Led::from_rgb(0, 0, 255);
+
+        assert!(green.luminance() > blue.luminance());
+        assert!((green.luminance() - 0.7152).abs() < 0.0001);
+        assert!((blue.luminance() - 0.0722).abs() < 0.0001);
+        assert_eq!(Led::new().luminance(), 0.0);
+        assert_eq!(Led::from_rgbw(255, 255, 255, 255).luminance(), 1.0);
+    }
+
+    #[test]
+    fn test_led_packed_u32_conversion() {
+        let led = Led::from(0xFF8800CCu32);
+        assert_eq!(led, Led::from_rgbw(0xFF, 0x88, 0x00, 0xCC));
+        assert_eq!(led.to_u32(), 0xFF8800CC);
+        assert_eq!(Led::from_u32(led.to_u32()), led);
+
+        let led_rgb = Led::from_rgb_u32(0x123456);
+        assert_eq!(led_rgb, Led::from_rgb(0x12, 0x34, 0x56));
+        assert_eq!(led_rgb.to_rgb_u32(), 0x123456);
+        assert_eq!(led_rgb.to_u32(), 0x12345600);
+    }
+
+    #[test]
+    fn test_led_add_overflow_modes() {
+        let led = Led::from_rgbw(255, 10, 0, 100);
+        let one = Led::from_rgbw(1, 1, 1, 1);
+
+        assert_eq!(led.saturating_add(one), Led::from_rgbw(255, 11, 1, 101));
+        assert_eq!(led.saturating_add(one), led + one);
+        assert_eq!(led.wrapping_add(one), Led::from_rgbw(0, 11, 1, 101));
+        assert_eq!(led.checked_add(one), None);
+        assert_eq!(
+            Led::from_rgbw(10, 20, 30, 40).checked_add(one),
+            Some(Led::from_rgbw(11, 21, 31, 41))
+        );
+    }
+
+    #[test]
+    fn test_led_saturation() {
+        let red = Led::from_rgbw(255, 0, 0, 40);
+
+        let gray = red.desaturate(1.0);
+        assert_eq!(gray.r, gray.g);
+        assert_eq!(gray.g, gray.b);
+        assert_eq!(gray.w, 40);
+
+        assert_eq!(gray.saturate(1.0), red);
+        assert_eq!(red.saturate(0.5), red);
+    }
+
+    #[test]
+    fn test_led_with_hsv_preserves_white() {
+        let led = Led::from_rgbw(255, 0, 0, 77);
+
+        let rotated = led.with_hsv(|hsv| hsv.hue += 120.0);
+        assert_eq!(rotated, Led::from_rgbw(0, 255, 0, 77));
+
+        let rotated = led.with_hsl(|hsl| hsl.hue += 240.0);
+        assert_eq!(rotated, Led::from_rgbw(0, 0, 255, 77));
+    }
+
+    #[test]
+    fn test_led_grayscale() {
+        let led = Led::from_rgbw(255, 0, 0, 10);
+        let expected = (0.2126f32 * 255.0).round() as u8;
+
+        let gray = led.grayscale();
+        assert_eq!(gray, Led::from_rgbw(expected, expected, expected, 10));
+
+        let white = led.grayscale_to_white();
+        assert_eq!(white, Led::from_rgbw(0, 0, 0, 10 + expected));
+
+        assert_eq!(
+            Led::from_rgb(255, 255, 255).grayscale(),
+            Led::from_rgb(255, 255, 255)
+        );
+    }
+
+    #[test]
+    fn test_led_distance() {
+        let black = Led::new();
+        let white = Led::from_rgbw(255, 255, 255, 255);
+        let red = Led::from_rgb(255, 0, 0);
+
+        assert_eq!(red.distance(&red), 0.0);
+        assert_eq!(black.distance(&white), 1.0);
+        assert_eq!(white.distance(&black), 1.0);
+        assert!(red.distance(&black) > 0.0 && red.distance(&black) < 1.0);
+
+        assert_eq!(red.perceptual_distance(&red), 0.0);
+        assert!(black.perceptual_distance(&white) > 99.0);
+        assert!(
+            Led::from_rgb(255, 0, 0).perceptual_distance(&Led::from_rgb(250, 0, 0))
+                < red.perceptual_distance(&Led::from_rgb(0, 0, 255))
+        );
+    }
+
+    #[test]
+    fn test_led_f32() {
+        let led = Led::from_rgbw(200, 200, 200, 200);
+
+        let mut quantized = led;
+        let mut accumulated = led.to_f32();
+        for _ in 0..10 {
+            quantized *= 0.9;
+            accumulated *= 0.9;
+        }
+
+        // 200 * 0.9^10 = ~69.74
+        assert_eq!(accumulated.to_led(), Led::from_rgbw(70, 70, 70, 70));
+        assert_ne!(quantized, accumulated.to_led());
+
+        assert_eq!(led.to_f32().to_led(), led);
+        assert_eq!((led.to_f32() * 2.0 - 500.0).to_led(), Led::new());
+        assert_eq!(
+            (led.to_f32() + LedF32::new(100.0, 0.0, 0.0, 0.0)).to_led(),
+            Led::from_rgbw(255, 200, 200, 200)
+        );
+    }
+
+    #[test]
+    fn test_led_clamp_brightness() {
+        assert_eq!(
+            Led::from_rgb(255, 128, 0).clamp_brightness(128),
+            Led::from_rgb(128, 64, 0)
+        );
+        assert_eq!(
+            Led::from_rgbw(10, 200, 50, 100).clamp_brightness(100),
+            Led::from_rgbw(5, 100, 25, 50)
+        );
+
+        let dim = Led::from_rgbw(100, 50, 0, 20);
+        assert_eq!(dim.clamp_brightness(128), dim);
+        assert_eq!(dim.clamp_brightness(0), Led::new());
+    }
+
+    #[test]
+    fn test_led_is_off() {
+        assert!(Led::new().is_off());
+        assert!(!Led::from_rgbw(0, 0, 0, 1).is_off());
+        assert!(!Led::from_rgb(0, 1, 0).is_off());
+    }
+
+    #[test]
+    #[cfg(feature = "smart-leds")]
+    fn test_led_smart_leds_conversion() {
+        let led = Led::from_rgbw(1, 2, 3, 4);
+
+        let color: smart_leds::RGBW<u8> = led.into();
+        assert_eq!((color.r, color.g, color.b, color.a.0), (1, 2, 3, 4));
+        assert_eq!(Led::from(color), led);
+    }
+
+    #[test]
+    fn test_led_add_linear() {
+        let gray = Led::from_rgbw(128, 128, 128, 128);
+        let mixed = gray.add_linear(gray);
+
+        assert_eq!(gray + gray, Led::from_rgbw(255, 255, 255, 255));
+        assert!(mixed.r > gray.r && mixed.r < 255);
+        assert_eq!(mixed, Led::from_rgbw(mixed.r, mixed.r, mixed.r, mixed.r));
+        assert_eq!(gray.add_linear(Led::default()), gray);
+        assert_eq!(
+            Led::from_rgbw(255, 0, 0, 200).add_linear(gray),
+            Led::from_rgbw(255, 128, 128, 230)
+        );
+    }
+
+    #[test]
+    fn test_led_mix_additive_clamped() {
+        let orange = Led::from_rgb(200, 100, 0);
+        let red = Led::from_rgb(200, 0, 50);
+
+        assert_eq!(orange + red, Led::from_rgb(255, 100, 50));
+        assert_eq!(orange.mix_additive_clamped(red), Led::from_rgb(255, 64, 32));
+        assert_eq!(
+            Led::from_rgb(10, 20, 30).mix_additive_clamped(Led::from_rgbw(1, 2, 3, 4)),
+            Led::from_rgbw(11, 22, 33, 4)
+        );
+    }
+
+    #[test]
+    fn test_led_f32_scaling_rounds() {
+        let led = Led::from_rgbw(10, 10, 10, 200);
+
+        assert_eq!(led * 0.19, Led::from_rgbw(2, 2, 2, 38));
+        assert_eq!(led / 4.0, Led::from_rgbw(3, 3, 3, 50));
+        assert_eq!(led * 2.0, Led::from_rgbw(20, 20, 20, 255));
+        assert_eq!(led * -1.0, Led::new());
+    }
+
+    #[test]
+    fn test_led_premultiply() {
+        let led = Led::from_rgbw(200, 100, 51, 255);
+        let premultiplied = led.premultiply(0.5);
+
+        assert_eq!(premultiplied, Led::from_rgbw(100, 50, 26, 128));
+        assert_eq!(led.premultiply(0.0), Led::new());
+        assert_eq!(led.premultiply(1.0), led);
+        assert!(premultiplied.unpremultiply(0.5).distance(&led) < 0.01);
+        assert_eq!(premultiplied.unpremultiply(0.0), Led::new());
+        assert_eq!(
+            Led::from_rgbw(200, 0, 0, 0).unpremultiply(0.5),
+            Led::from_rgbw(255, 0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_led_from_kelvin() {
+        let daylight = Led::from_kelvin(6500);
+        assert!(daylight.w > 240);
+        assert!(daylight.r < 10 && daylight.g < 10 && daylight.b < 10);
+
+        let warm = Led::from_kelvin(2700);
+        assert!(warm.r > warm.g && warm.g > warm.b);
+        assert!(warm.w > 0);
+
+        assert_eq!(Led::from_kelvin(0), Led::from_kelvin(1000));
+        assert_eq!(Led::from_kelvin(u16::MAX), Led::from_kelvin(12000));
+    }
+
+    #[test]
+    fn test_led_boost_white() {
+        let gray = Led::from_rgb(100, 100, 100);
+        assert_eq!(gray.boost_white(1.0), Led::from_rgbw(100, 100, 100, 100));
+        assert_eq!(gray.boost_white(0.5), Led::from_rgbw(100, 100, 100, 50));
+
+        let tinted = Led::from_rgbw(200, 150, 100, 200);
+        assert_eq!(tinted.boost_white(1.0), Led::from_rgbw(200, 150, 100, 255));
+        assert_eq!(tinted.boost_white(0.0), tinted);
+    }
+
+    #[test]
+    fn test_led_hash_and_ord() {
+        let red = Led::from_rgb(255, 0, 0);
+        let green = Led::from_rgb(0, 255, 0);
+        let white = Led::from_rgbw(0, 0, 0, 255);
+
+        let set: std::collections::HashSet<Led> = [red, green, red, white].into_iter().collect();
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&green));
+
+        let mut leds = vec![red, white, green, Led::new()];
+        leds.sort();
+        assert_eq!(leds, [Led::new(), white, green, red]);
+        assert!(Led::from_rgbw(1, 0, 0, 0) > Led::from_rgbw(0, 255, 255, 255));
+    }
+
+    #[test]
+    fn test_led_with_channel() {
+        let led = Led::from_rgbw(1, 2, 3, 4);
+
+        assert_eq!(led.with_r(10), Led::from_rgbw(10, 2, 3, 4));
+        assert_eq!(led.with_g(10), Led::from_rgbw(1, 10, 3, 4));
+        assert_eq!(led.with_b(10), Led::from_rgbw(1, 2, 10, 4));
+        assert_eq!(led.with_w(10), Led::from_rgbw(1, 2, 3, 10));
+        assert_eq!(
+            Led::new().with_r(255).with_w(64),
+            Led::from_rgbw(255, 0, 0, 64)
+        );
+    }
+
+    #[test]
+    fn test_led_swizzle() {
+        let led = Led::from_rgbw(1, 2, 3, 4);
+
+        assert_eq!(led.swap_rg(), Led::from_rgbw(2, 1, 3, 4));
+        assert_eq!(led.swap_rb(), Led::from_rgbw(3, 2, 1, 4));
+        assert_eq!(led.swap_rg().swap_rg(), led);
+        assert_eq!(
+            led.swizzle([Channel::W, Channel::B, Channel::G, Channel::R]),
+            Led::from_rgbw(4, 3, 2, 1)
+        );
+        assert_eq!(
+            led.swizzle([Channel::R, Channel::G, Channel::B, Channel::W]),
+            led
+        );
+        assert_eq!(led.swizzle([Channel::G; 4]), Led::from_rgbw(2, 2, 2, 2));
+    }
 }
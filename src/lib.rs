@@ -1,2 +1,11 @@
 pub mod strip;
-pub mod led;
\ No newline at end of file
+pub mod led;
+pub mod colors;
+pub mod segment;
+pub mod frame;
+pub mod effect;
+pub mod matrix;
+#[cfg(feature = "rppal")]
+pub mod spi;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
@@ -0,0 +1,439 @@
+use crate::led::Led;
+#[cfg(feature = "rppal")]
+use crate::spi::RppalSpi;
+use crate::strip::{LengthError, Strip};
+use std::{error::Error, fmt};
+
+/// Width of a single character of the built-in font, in pixels
+pub const GLYPH_WIDTH: usize = 3;
+/// Height of a single character of the built-in font, in pixels
+pub const GLYPH_HEIGHT: usize = 5;
+/// Horizontal distance between the beginnings of consecutive characters, in pixels
+pub const GLYPH_ADVANCE: usize = GLYPH_WIDTH + 1;
+
+/// Error returned when trying to access pixel outside of the matrix
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PixelError {
+    /// Column that was accessed
+    pub x: usize,
+    /// Row that was accessed
+    pub y: usize,
+    /// Width of the matrix
+    pub width: usize,
+    /// Height of the matrix
+    pub height: usize,
+}
+
+impl fmt::Display for PixelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "pixel ({}, {}) is out of range for {}x{} matrix",
+            self.x, self.y, self.width, self.height
+        )
+    }
+}
+
+impl Error for PixelError {}
+
+/// The way rows of the matrix are wired together
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MatrixLayout {
+    /// Every row starts on the same side (left), so the data line goes back after each row
+    #[default]
+    Progressive,
+    /// Rows alternate direction, odd rows (counting from 0) go right-to-left
+    Serpentine,
+}
+
+/// 2D LED panel made from a strip split into rows, addressed by `(x, y)` coordinates.
+/// Pixel `(0, 0)` is the first LED of the strip.
+#[derive(Debug)]
+pub struct Matrix<#[cfg(feature = "rppal")] SPI = RppalSpi, #[cfg(not(feature = "rppal"))] SPI> {
+    strip: Strip<SPI>,
+    width: usize,
+    height: usize,
+    layout: MatrixLayout,
+}
+
+impl<SPI> Matrix<SPI> {
+    /// Create new matrix of `width` x `height` pixels from a strip. Returns an error if the strip is too short.
+    /// LEDs past `width * height` are not addressed by the matrix.
+    pub fn new(
+        strip: Strip<SPI>,
+        width: usize,
+        height: usize,
+        layout: MatrixLayout,
+    ) -> Result<Self, LengthError> {
+        // Dimensions with overflowing pixel count can't fit in any strip
+        width
+            .checked_mul(height)
+            .filter(|&pixels| pixels <= strip.len())
+            .ok_or(LengthError {
+                expected: width.saturating_mul(height),
+                actual: strip.len(),
+            })?;
+
+        Ok(Self {
+            strip,
+            width,
+            height,
+            layout,
+        })
+    }
+
+    /// Get the width of the matrix, in pixels
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Get the height of the matrix, in pixels
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Get the layout of the matrix rows
+    pub fn layout(&self) -> MatrixLayout {
+        self.layout
+    }
+
+    /// Get the underlying strip
+    pub fn strip(&self) -> &Strip<SPI> {
+        &self.strip
+    }
+
+    /// Get the underlying strip mutably, for example to call `update` or `fill`
+    pub fn strip_mut(&mut self) -> &mut Strip<SPI> {
+        &mut self.strip
+    }
+
+    /// Get the underlying strip back
+    pub fn into_strip(self) -> Strip<SPI> {
+        self.strip
+    }
+
+    /// Get the index of LED in the strip for pixel `(x, y)`, or `None` if it's outside of the matrix
+    pub fn index(&self, x: usize, y: usize) -> Option<usize> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let x = match self.layout {
+            MatrixLayout::Serpentine if y % 2 == 1 => self.width - 1 - x,
+            _ => x,
+        };
+        Some(y * self.width + x)
+    }
+
+    /// Get the color of pixel `(x, y)`, or `None` if it's outside of the matrix
+    pub fn get_pixel(&self, x: usize, y: usize) -> Option<Led> {
+        self.index(x, y).and_then(|index| self.strip.get(index))
+    }
+
+    /// Set the color of pixel `(x, y)`. Returns an error if it's outside of the matrix.
+    pub fn set_pixel(&mut self, x: usize, y: usize, led: Led) -> Result<(), PixelError> {
+        let index = self.index(x, y).ok_or(PixelError {
+            x,
+            y,
+            width: self.width,
+            height: self.height,
+        })?;
+        self.strip[index] = led;
+        Ok(())
+    }
+
+    /// Draw a straight line from `(x0, y0)` to `(x1, y1)` using Bresenham's algorithm.
+    /// Coordinates may lie outside of the matrix, in which case the line is clipped to it before drawing.
+    pub fn draw_line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, led: Led) {
+        let Some([x0, y0, x1, y1]) = self.clip_line([x0, y0, x1, y1].map(i64::from)) else {
+            return;
+        };
+
+        let dx = (x1 - x0).abs();
+        let dy = -(y1 - y0).abs();
+        let step_x = if x0 < x1 { 1 } else { -1 };
+        let step_y = if y0 < y1 { 1 } else { -1 };
+        let (mut x, mut y) = (x0, y0);
+        let mut error = dx + dy;
+
+        loop {
+            self.set_pixel_clipped(x, y, led);
+            if x == x1 && y == y1 {
+                break;
+            }
+
+            let doubled_error = 2 * error;
+            if doubled_error >= dy {
+                error += dy;
+                x += step_x;
+            }
+            if doubled_error <= dx {
+                error += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    /// Draw `text` with built-in 3x5 font in the top rows of the matrix, starting at column `x_offset`.
+    /// Decrease `x_offset` every frame to scroll the text to the left. Only lit pixels of characters are drawn,
+    /// the background is left untouched. Letters are drawn uppercase, unsupported characters are left blank.
+    pub fn draw_text(&mut self, text: &str, x_offset: i32, color: Led) {
+        let width = i64::try_from(self.width).unwrap_or(i64::MAX);
+        for (index, character) in text.chars().enumerate() {
+            let glyph_x = (index as i64)
+                .checked_mul(GLYPH_ADVANCE as i64)
+                .and_then(|advance| advance.checked_add(i64::from(x_offset)));
+            // Following characters are past the right edge of the matrix too
+            let Some(glyph_x) = glyph_x.filter(|&glyph_x| glyph_x < width) else {
+                break;
+            };
+
+            for (y, row) in glyph(character).into_iter().enumerate() {
+                for column in 0..GLYPH_WIDTH {
+                    if row & (1 << (GLYPH_WIDTH - 1 - column)) != 0 {
+                        self.set_pixel_clipped(glyph_x + column as i64, y as i64, color);
+                    }
+                }
+            }
+        }
+    }
+
+    // Clip line from `(x0, y0)` to `(x1, y1)` to the matrix bounds using Liang-Barsky algorithm.
+    // Returns `None` if the line lies entirely outside of the matrix.
+    fn clip_line(&self, [x0, y0, x1, y1]: [i64; 4]) -> Option<[i64; 4]> {
+        if self.width == 0 || self.height == 0 {
+            return None;
+        }
+
+        let (max_x, max_y) = ((self.width - 1) as f64, (self.height - 1) as f64);
+        let (dx, dy) = ((x1 - x0) as f64, (y1 - y0) as f64);
+        let (mut t0, mut t1) = (0.0_f64, 1.0_f64);
+        // Every edge of the matrix is described by `p * t <= q` inequality
+        let edges = [
+            (-dx, x0 as f64),
+            (dx, max_x - x0 as f64),
+            (-dy, y0 as f64),
+            (dy, max_y - y0 as f64),
+        ];
+        for (p, q) in edges {
+            if p == 0.0 {
+                if q < 0.0 {
+                    return None;
+                }
+            } else if p < 0.0 {
+                t0 = t0.max(q / p);
+            } else {
+                t1 = t1.min(q / p);
+            }
+        }
+        if t0 > t1 {
+            return None;
+        }
+
+        let point = |t: f64| {
+            [
+                (x0 as f64 + t * dx).round().clamp(0.0, max_x) as i64,
+                (y0 as f64 + t * dy).round().clamp(0.0, max_y) as i64,
+            ]
+        };
+        let ([x0, y0], [x1, y1]) = (point(t0), point(t1));
+        Some([x0, y0, x1, y1])
+    }
+
+    // Set the color of pixel at signed coordinates, ignoring pixels outside of the matrix
+    fn set_pixel_clipped(&mut self, x: i64, y: i64, led: Led) {
+        if let (Ok(x), Ok(y)) = (usize::try_from(x), usize::try_from(y)) {
+            let _ = self.set_pixel(x, y, led);
+        }
+    }
+}
+
+/// Get the width of `text` drawn with `Matrix::draw_text`, in pixels
+pub fn text_width(text: &str) -> usize {
+    (text.chars().count() * GLYPH_ADVANCE).saturating_sub(1)
+}
+
+// Get rows of built-in font character, top to bottom. Every row has 3 bits, the highest one is the leftmost pixel.
+fn glyph(character: char) -> [u8; GLYPH_HEIGHT] {
+    match character.to_ascii_uppercase() {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+        'H' => [0b101, 0b101, 0b111, 0b101, 0b101],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+        'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'Q' => [0b010, 0b101, 0b101, 0b110, 0b011],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+        'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+        'X' => [0b101, 0b101, 0b010, 0b101, 0b101],
+        'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+        'Z' => [0b111, 0b001, 0b010, 0b100, 0b111],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        ',' => [0b000, 0b000, 0b000, 0b010, 0b100],
+        '!' => [0b010, 0b010, 0b010, 0b000, 0b010],
+        '?' => [0b111, 0b001, 0b010, 0b000, 0b010],
+        ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '+' => [0b000, 0b010, 0b111, 0b010, 0b000],
+        '/' => [0b001, 0b001, 0b010, 0b100, 0b100],
+        '\'' => [0b010, 0b010, 0b000, 0b000, 0b000],
+        _ => [0; GLYPH_HEIGHT],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockSpi;
+
+    #[test]
+    fn test_progressive_layout() {
+        let matrix = Matrix::new(Strip::new_mock(12), 4, 3, MatrixLayout::Progressive).unwrap();
+
+        assert_eq!(matrix.index(0, 0), Some(0));
+        assert_eq!(matrix.index(3, 0), Some(3));
+        assert_eq!(matrix.index(0, 1), Some(4));
+        assert_eq!(matrix.index(3, 1), Some(7));
+        assert_eq!(matrix.index(0, 2), Some(8));
+        assert_eq!(matrix.index(3, 2), Some(11));
+        assert_eq!(matrix.index(4, 0), None);
+        assert_eq!(matrix.index(0, 3), None);
+    }
+
+    #[test]
+    fn test_serpentine_layout() {
+        let matrix = Matrix::new(Strip::new_mock(12), 4, 3, MatrixLayout::Serpentine).unwrap();
+
+        assert_eq!(matrix.index(0, 0), Some(0));
+        assert_eq!(matrix.index(3, 0), Some(3));
+        assert_eq!(matrix.index(0, 1), Some(7));
+        assert_eq!(matrix.index(3, 1), Some(4));
+        assert_eq!(matrix.index(0, 2), Some(8));
+        assert_eq!(matrix.index(3, 2), Some(11));
+    }
+
+    #[test]
+    fn test_set_pixel() {
+        let led = Led::from_rgb(1, 2, 3);
+        let mut matrix = Matrix::new(Strip::new_mock(4), 2, 2, MatrixLayout::Serpentine).unwrap();
+
+        matrix.set_pixel(0, 1, led).unwrap();
+        assert_eq!(matrix.get_pixel(0, 1), Some(led));
+        assert_eq!(matrix.strip()[3], led);
+        assert_eq!(matrix.get_pixel(2, 0), None);
+        assert_eq!(
+            matrix.set_pixel(2, 0, led),
+            Err(PixelError {
+                x: 2,
+                y: 0,
+                width: 2,
+                height: 2
+            })
+        );
+
+        assert!(Matrix::new(Strip::new_mock(3), 2, 2, MatrixLayout::Progressive).is_err());
+        assert_eq!(
+            Matrix::new(Strip::new_mock(4), usize::MAX, 2, MatrixLayout::Progressive).unwrap_err(),
+            LengthError {
+                expected: usize::MAX,
+                actual: 4
+            }
+        );
+    }
+
+    // Get coordinates of all lit pixels, row by row
+    fn lit_pixels(matrix: &Matrix<MockSpi>) -> Vec<(usize, usize)> {
+        (0..matrix.height())
+            .flat_map(|y| (0..matrix.width()).map(move |x| (x, y)))
+            .filter(|&(x, y)| !matrix.get_pixel(x, y).unwrap().is_off())
+            .collect()
+    }
+
+    #[test]
+    fn test_draw_line() {
+        let led = Led::from_rgb(255, 0, 0);
+        let mut matrix = Matrix::new(Strip::new_mock(16), 4, 4, MatrixLayout::Serpentine).unwrap();
+
+        matrix.draw_line(0, 1, 3, 1, led);
+        assert_eq!(lit_pixels(&matrix), [(0, 1), (1, 1), (2, 1), (3, 1)]);
+
+        matrix.strip_mut().clear();
+        matrix.draw_line(2, 3, 2, 0, led);
+        assert_eq!(lit_pixels(&matrix), [(2, 0), (2, 1), (2, 2), (2, 3)]);
+
+        matrix.strip_mut().clear();
+        matrix.draw_line(0, 0, 3, 3, led);
+        assert_eq!(lit_pixels(&matrix), [(0, 0), (1, 1), (2, 2), (3, 3)]);
+
+        matrix.strip_mut().clear();
+        matrix.draw_line(-2, 1, 10, 1, led);
+        assert_eq!(lit_pixels(&matrix), [(0, 1), (1, 1), (2, 1), (3, 1)]);
+
+        matrix.strip_mut().clear();
+        matrix.draw_line(-2, -2, 5, 5, led);
+        assert_eq!(lit_pixels(&matrix), [(0, 0), (1, 1), (2, 2), (3, 3)]);
+
+        matrix.strip_mut().clear();
+        matrix.draw_line(-2_000_000_000, 2, 2_000_000_000, 2, led);
+        assert_eq!(lit_pixels(&matrix), [(0, 2), (1, 2), (2, 2), (3, 2)]);
+
+        matrix.strip_mut().clear();
+        matrix.draw_line(i32::MIN, -1, i32::MAX, -1, led);
+        matrix.draw_line(i32::MIN, i32::MIN, -1, i32::MAX, led);
+        assert!(lit_pixels(&matrix).is_empty());
+    }
+
+    #[test]
+    fn test_draw_text() {
+        let color = Led::from_rgb(0, 255, 0);
+        let mut matrix = Matrix::new(Strip::new_mock(48), 8, 6, MatrixLayout::Serpentine).unwrap();
+
+        matrix.draw_text("T", 2, color);
+        assert_eq!(
+            lit_pixels(&matrix),
+            [(2, 0), (3, 0), (4, 0), (3, 1), (3, 2), (3, 3), (3, 4)]
+        );
+
+        matrix.strip_mut().clear();
+        matrix.draw_text("~T", -6, color);
+        assert_eq!(lit_pixels(&matrix), [(0, 0)]);
+
+        matrix.strip_mut().clear();
+        matrix.draw_text("AB", i32::MAX - 1, color);
+        matrix.draw_text("A", 8, color);
+        assert!(lit_pixels(&matrix).is_empty());
+
+        matrix.draw_text("TT", 6, color);
+        assert_eq!(
+            lit_pixels(&matrix),
+            [(6, 0), (7, 0), (7, 1), (7, 2), (7, 3), (7, 4)]
+        );
+
+        assert_eq!(text_width("AB"), 7);
+        assert_eq!(text_width(""), 0);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::strip::Strip;
+use embedded_hal::spi::{ErrorType, SpiBus};
+use std::convert::Infallible;
+
+/// Amount of most recent frames kept by `MockSpi`
+pub const FRAME_HISTORY_LEN: usize = 16;
+
+/// In-memory SPI bus capturing the data sent to the strip, for testing effects without hardware.
+/// Bytes written between flushes are treated as a single frame. Only the last `FRAME_HISTORY_LEN` frames are kept,
+/// so memory usage stays constant in long-running effect loops.
+#[derive(Debug, Default)]
+pub struct MockSpi {
+    pending_frame: Vec<u8>,
+    pending_writes: usize,
+    frames: Vec<Vec<u8>>,
+    last_frame_writes: usize,
+}
+
+impl MockSpi {
+    /// Create new mock SPI bus
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Get the bytes of the last complete frame sent to the bus
+    pub fn last_frame(&self) -> &[u8] {
+        self.frames.last().map_or(&[], Vec::as_slice)
+    }
+
+    /// Get the bytes of up to `FRAME_HISTORY_LEN` most recent complete frames sent to the bus, oldest first
+    pub fn frames(&self) -> &[Vec<u8>] {
+        &self.frames
+    }
+
+    /// Get the amount of writes the last complete frame was sent in
+    pub fn last_frame_writes(&self) -> usize {
+        self.last_frame_writes
+    }
+}
+
+impl ErrorType for MockSpi {
+    type Error = Infallible;
+}
+
+impl SpiBus for MockSpi {
+    fn read(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        words.fill(0);
+        Ok(())
+    }
+
+    fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+        self.pending_frame.extend_from_slice(words);
+        self.pending_writes += 1;
+        Ok(())
+    }
+
+    fn transfer(&mut self, read: &mut [u8], write: &[u8]) -> Result<(), Self::Error> {
+        read.fill(0);
+        self.write(write)
+    }
+
+    fn transfer_in_place(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        self.write(words)?;
+        words.fill(0);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        if self.frames.len() == FRAME_HISTORY_LEN {
+            self.frames.remove(0);
+        }
+        self.frames.push(std::mem::take(&mut self.pending_frame));
+        self.last_frame_writes = std::mem::take(&mut self.pending_writes);
+        Ok(())
+    }
+}
+
+impl Strip<MockSpi> {
+    /// Create new strip backed by in-memory mock SPI bus, which doesn't require any hardware
+    pub fn new_mock(amount_of_leds: usize) -> Self {
+        Self::from_spi_bus(MockSpi::new(), amount_of_leds)
+    }
+
+    /// Get the bytes of the last frame sent by `update`
+    pub fn last_frame(&self) -> &[u8] {
+        self.spi().last_frame()
+    }
+
+    /// Get the bytes of up to `FRAME_HISTORY_LEN` most recent frames sent by `update`, oldest first
+    pub fn frames(&self) -> &[Vec<u8>] {
+        self.spi().frames()
+    }
+
+    /// Get the amount of SPI writes the last frame was sent in
+    pub fn last_frame_writes(&self) -> usize {
+        self.spi().last_frame_writes()
+    }
+}
@@ -0,0 +1,461 @@
+use crate::led::Led;
+use crate::strip::gamma_table;
+use palette::{Gradient, Hsv, LinSrgb, Mix, Srgb};
+use rand::{seq::index, Rng};
+use std::{
+    ops::{Bound, Index, IndexMut, Range, RangeBounds},
+    slice,
+};
+
+/// Mutable view of a continuous range of LEDs in the strip, for driving independent zones.
+/// Created with `Strip::segment`, use `split_at` to get multiple disjoint segments at once.
+#[derive(Debug)]
+pub struct Segment<'a> {
+    leds: &'a mut [Led],
+}
+
+impl<'a> Segment<'a> {
+    /// Create new segment covering provided LEDs
+    pub fn new(leds: &'a mut [Led]) -> Self {
+        Self { leds }
+    }
+
+    /// Get the amount of LEDs in the segment
+    pub fn len(&self) -> usize {
+        self.leds.len()
+    }
+
+    /// Check if the segment has no LEDs
+    pub fn is_empty(&self) -> bool {
+        self.leds.is_empty()
+    }
+
+    /// Iterate over the LEDs of the segment
+    pub fn iter(&self) -> slice::Iter<'_, Led> {
+        self.leds.iter()
+    }
+
+    /// Iterate mutably over the LEDs of the segment
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, Led> {
+        self.leds.iter_mut()
+    }
+
+    /// Split the segment into two disjoint segments, the first one containing LEDs `0..mid`.
+    /// Panics if `mid` is greater than the length of the segment.
+    pub fn split_at(self, mid: usize) -> (Segment<'a>, Segment<'a>) {
+        let (left, right) = self.leds.split_at_mut(mid);
+        (Segment::new(left), Segment::new(right))
+    }
+
+    /// Set the color of all LEDs in the segment
+    pub fn fill(&mut self, led: Led) {
+        self.leds.fill(led);
+    }
+
+    /// Set the color of LEDs in specified range of the segment, for example `2..5`, `2..=4` or `..`.
+    /// Parts of the range outside of the segment are ignored.
+    pub fn fill_range(&mut self, range: impl RangeBounds<usize>, led: Led) {
+        let range = clamp_range(range, self.leds.len());
+        self.leds[range].fill(led);
+    }
+
+    /// Turn off all LEDs in the segment
+    pub fn clear(&mut self) {
+        self.fill(Led::new());
+    }
+
+    /// Fill the segment with a gradient from `start` color on the first LED to `end` color on the last one.
+    /// RGB channels are interpolated in linear RGB space, white channel is interpolated separately.
+    pub fn fill_gradient(&mut self, start: Led, end: Led) {
+        let start_linear = Srgb::from(start).into_linear();
+        let end_linear = Srgb::from(end).into_linear();
+        let steps = self.leds.len().saturating_sub(1).max(1) as f32;
+
+        self.leds.iter_mut().enumerate().for_each(|(index, led)| {
+            let t = index as f32 / steps;
+            let color: Srgb<u8> = Srgb::from_linear(start_linear.mix(&end_linear, t)).into_format();
+            *led = Led::from_rgbw(color.red, color.green, color.blue, start.lerp(end, t).w);
+        });
+    }
+
+    /// Fill the segment with a gradient defined by `(position, color)` stops, with positions in 0.0..=1.0 range
+    /// (0.0 is the first LED, 1.0 is the last one) sorted in ascending order. All channels, including white,
+    /// are interpolated linearly between neighbouring stops. LEDs before the first stop and after the last one
+    /// get the color of the nearest stop. Empty `stops` leave the segment unchanged.
+    pub fn fill_gradient_stops(&mut self, stops: &[(f32, Led)]) {
+        let (Some(&(_, first)), Some(&(_, last))) = (stops.first(), stops.last()) else {
+            return;
+        };
+        let steps = self.leds.len().saturating_sub(1).max(1) as f32;
+
+        self.leds.iter_mut().enumerate().for_each(|(index, led)| {
+            let t = index as f32 / steps;
+            *led = match stops.iter().position(|&(position, _)| position >= t) {
+                Some(0) => first,
+                Some(next) => {
+                    let (start_position, start) = stops[next - 1];
+                    let (end_position, end) = stops[next];
+                    let span = end_position - start_position;
+                    if span > 0.0 {
+                        start.lerp(end, (t - start_position) / span)
+                    } else {
+                        end
+                    }
+                }
+                None => last,
+            };
+        });
+    }
+
+    /// Fill the segment with a rainbow, starting from `start_hue` (in degrees) and changing the hue by `hue_step` degrees for each next LED.
+    /// Colors have full saturation and value, hue wraps around after 360 degrees.
+    pub fn fill_rainbow(&mut self, start_hue: f32, hue_step: f32) {
+        self.leds.iter_mut().enumerate().for_each(|(index, led)| {
+            let hue = (start_hue + hue_step * index as f32).rem_euclid(360.0);
+            *led = Hsv::new(hue, 1.0, 1.0).into();
+        });
+    }
+
+    /// Scale HSV value (brightness) of every LED by `value`, keeping its hue and saturation.
+    /// White channel is not modified. Colors stored in `leds` are changed, unlike with `set_brightness`.
+    pub fn set_value(&mut self, value: f32) {
+        self.leds
+            .iter_mut()
+            .for_each(|led| *led = led.with_hsv(|hsv| hsv.value *= value));
+    }
+
+    /// Display `level` (clamped to 0.0..=1.0) as a bar of lit LEDs starting at the beginning of the segment, turning off the rest.
+    /// Color of every LED depends on its position, changing from `low` on the first LED to `high` on the last one.
+    /// The LED at the end of the bar is partially lit, proportionally to the fractional part of the level.
+    pub fn vu_meter(&mut self, level: f32, low: Led, high: Led) {
+        let lit = level.clamp(0.0, 1.0) * self.leds.len() as f32;
+        let steps = self.leds.len().saturating_sub(1).max(1) as f32;
+
+        self.leds.iter_mut().enumerate().for_each(|(index, led)| {
+            let coverage = (lit - index as f32).clamp(0.0, 1.0);
+            *led = low.lerp(high, index as f32 / steps) * coverage;
+        });
+    }
+
+    /// Dim all LEDs by subtracting `amount` from every channel, saturating at 0.
+    /// Call it every frame before drawing new pixels to get trailing decay effects.
+    pub fn fade_to_black_by(&mut self, amount: u8) {
+        self.leds.iter_mut().for_each(|led| *led -= amount);
+    }
+
+    /// Fill the segment with `color` when `on` is true, turn it off otherwise.
+    /// Toggle `on` every interval to get a blinking indicator.
+    pub fn blink(&mut self, color: Led, on: bool) {
+        self.blink_range(.., color, on);
+    }
+
+    /// Same as `blink`, but only for LEDs in specified range. LEDs outside of the range are not modified.
+    pub fn blink_range(&mut self, range: impl RangeBounds<usize>, color: Led, on: bool) {
+        self.fill_range(range, if on { color } else { Led::new() });
+    }
+
+    /// Fill the segment with `base` color scaled by sinusoidal brightness `(sin(phase) + 1) / 2`.
+    /// Advance `phase` over time to get a breathing effect.
+    pub fn apply_breathing(&mut self, base: Led, phase: f32) {
+        self.fill(base * ((phase.sin() + 1.0) / 2.0));
+    }
+
+    /// Light every `spacing`-th LED with `color` and turn off the rest. LED `i` is lit when `(i + offset) % spacing == 0`.
+    /// Increment `offset` every frame to get a theater chase (marquee) effect. `spacing` of 0 is treated as 1.
+    pub fn theater_chase(&mut self, color: Led, spacing: usize, offset: usize) {
+        let spacing = spacing.max(1);
+        self.leds.iter_mut().enumerate().for_each(|(index, led)| {
+            *led = if (index + offset).is_multiple_of(spacing) {
+                color
+            } else {
+                Led::new()
+            };
+        });
+    }
+
+    /// Draw a comet with full `color` at `head` index and a fading tail of `tail_len` LEDs behind it (at lower indexes).
+    /// Each next tail LED loses `decay` (0.0..=1.0) part of the previous one's brightness. All other LEDs are turned off.
+    /// `head` can be outside of the segment, in which case only the visible part of the tail is drawn.
+    pub fn comet(&mut self, head: usize, color: Led, tail_len: usize, decay: f32) {
+        let fade = 1.0 - decay.clamp(0.0, 1.0);
+        self.clear();
+
+        let mut brightness = 1.0;
+        for index in (head.saturating_sub(tail_len)..=head).rev() {
+            if let Some(led) = self.leds.get_mut(index) {
+                *led = color * brightness;
+            }
+            brightness *= fade;
+        }
+    }
+
+    /// Draw a symmetric glowing block with full `color` at `center` index, fading out linearly over `width` LEDs on both sides.
+    /// LED at distance `d` from the center gets `1 - d / width` of the color brightness. All other LEDs are turned off.
+    /// Move `center` back and forth every frame to get the Larson scanner effect. Parts outside of the segment are not drawn.
+    pub fn larson_scanner(&mut self, center: usize, width: usize, color: Led) {
+        self.clear();
+
+        let width = width.max(1);
+        let start = center.saturating_sub(width - 1);
+        let end = center
+            .saturating_add(width - 1)
+            .min(self.leds.len().saturating_sub(1));
+        for index in start..=end {
+            if let Some(led) = self.leds.get_mut(index) {
+                let distance = index.abs_diff(center) as f32;
+                *led = color * (1.0 - distance / width as f32);
+            }
+        }
+    }
+
+    /// Light a random `density` (0.0..=1.0) fraction of LEDs with `color`, leaving the rest untouched.
+    /// Randomness comes from provided `rng`, so the effect is deterministic for seeded generators.
+    pub fn twinkle(&mut self, color: Led, density: f32, rng: &mut impl Rng) {
+        let amount = (self.leds.len() as f32 * density.clamp(0.0, 1.0)).round() as usize;
+        index::sample(rng, self.leds.len(), amount)
+            .into_iter()
+            .for_each(|index| self.leds[index] = color);
+    }
+
+    /// Set all LEDs up to and including `position` to `color` and turn off the rest.
+    /// Increment `position` every frame to get a color wipe effect. Positions past the end light the whole segment.
+    pub fn color_wipe_step(&mut self, color: Led, position: usize) {
+        let lit = position.saturating_add(1).min(self.leds.len());
+        self.leds[..lit].fill(color);
+        self.leds[lit..].fill(Led::new());
+    }
+
+    /// Set the segment LED colors to a `palette` gradient
+    pub fn set_gradient(&mut self, gradient: Gradient<LinSrgb>) {
+        gradient
+            .take(self.leds.len())
+            .zip(self.leds.iter_mut())
+            .for_each(|(color, led)| {
+                *led = Srgb::from_linear(color).into();
+            });
+    }
+
+    /// Set the segment LED colors to a `palette` gradient, with gamma correction applied to every color before storing it.
+    /// Use it to get perceptually smooth brightness ramps, as LED PWM output is linear. See `Strip::set_gamma` for transmit-time correction.
+    pub fn set_gradient_gamma(&mut self, gradient: Gradient<LinSrgb>, gamma: f32) {
+        let table = gamma_table(gamma);
+        self.set_gradient(gradient);
+        self.leds.iter_mut().for_each(|led| {
+            *led = Led::from_rgbw(
+                table[led.r as usize],
+                table[led.g as usize],
+                table[led.b as usize],
+                table[led.w as usize],
+            );
+        });
+    }
+
+    /// Fill the segment with a rainbow spanning `hue_span` degrees, starting from `phase` (in degrees) on the first LED.
+    /// Advance `phase` every frame to scroll the rainbow. Colors are recomputed on every call, so there's no rounding drift.
+    pub fn animate_rainbow(&mut self, phase: f32, hue_span: f32) {
+        let hue_step = hue_span / self.leds.len().max(1) as f32;
+        self.fill_rainbow(phase, hue_step);
+    }
+
+    /// Fill the segment with colors sampled from `palette` treated as a looping gradient, interpolating between entries.
+    /// The whole palette spans the segment once, `phase` (in palette entries) shifts it - advance it every frame to scroll.
+    /// Empty palette turns the segment off.
+    pub fn cycle_palette(&mut self, palette: &[Led], phase: f32) {
+        if palette.is_empty() {
+            self.clear();
+            return;
+        }
+
+        let step = palette.len() as f32 / self.leds.len().max(1) as f32;
+        self.leds.iter_mut().enumerate().for_each(|(index, led)| {
+            let position = (index as f32 * step + phase).rem_euclid(palette.len() as f32);
+            let current = (position.floor() as usize).min(palette.len() - 1);
+            let next = (current + 1) % palette.len();
+            *led = palette[current].lerp(palette[next], position.fract());
+        });
+    }
+
+    /// Fill the segment with a plasma effect - hue of every LED is computed from a sum of sine waves of its position and `time`.
+    /// Advance `time` every frame to animate it. `scale` sets the spatial frequency, higher values give more color bands.
+    pub fn plasma_step(&mut self, time: f32, scale: f32) {
+        self.leds.iter_mut().enumerate().for_each(|(index, led)| {
+            let x = index as f32 * scale;
+            let value =
+                (x + time).sin() + (x * 0.5 - time * 1.3).sin() + ((x + time * 0.7) * 0.25).sin();
+            // Sum of three sines is in -3.0..=3.0 range
+            let hue = (value + 3.0) / 6.0 * 360.0;
+            *led = Hsv::new(hue, 1.0, 1.0).into();
+        });
+    }
+
+    /// Reflect the first half of the segment onto the second half, so LED `i` is copied to LED `len - 1 - i`.
+    /// For odd amount of LEDs, the middle one is left untouched.
+    pub fn mirror(&mut self) {
+        let len = self.leds.len();
+        (0..len / 2).for_each(|index| self.leds[len - 1 - index] = self.leds[index]);
+    }
+
+    /// Reverse the order of LEDs in the segment
+    pub fn reverse(&mut self) {
+        self.leds.reverse();
+    }
+
+    /// Move LEDs `count` positions towards the beginning of the segment, without wrapping.
+    /// LEDs shifted past the beginning are dropped and vacated LEDs at the end are set to `fill`.
+    pub fn scroll_left(&mut self, count: usize, fill: Led) {
+        let count = count.min(self.leds.len());
+        let len = self.leds.len();
+        self.leds.copy_within(count.., 0);
+        self.leds[len - count..].fill(fill);
+    }
+
+    /// Move LEDs `count` positions towards the end of the segment, without wrapping.
+    /// LEDs shifted past the end are dropped and vacated LEDs at the beginning are set to `fill`.
+    pub fn scroll_right(&mut self, count: usize, fill: Led) {
+        let count = count.min(self.leds.len());
+        let len = self.leds.len();
+        self.leds.copy_within(..len - count, count);
+        self.leds[..count].fill(fill);
+    }
+
+    /// Rotate LEDs `count` positions towards the beginning of the segment, wrapping them around to the end
+    pub fn shift_left(&mut self, count: usize) {
+        self.leds.rotate_left(count);
+    }
+
+    /// Rotate LEDs `count` positions towards the end of the segment, wrapping them around to the beginning
+    pub fn shift_right(&mut self, count: usize) {
+        self.leds.rotate_right(count);
+    }
+}
+
+// Convert any range into a range of valid indexes for `len` LEDs
+pub(crate) fn clamp_range(range: impl RangeBounds<usize>, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.saturating_add(1),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.saturating_add(1),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+
+    let end = end.min(len);
+    start.min(end)..end
+}
+
+impl Index<usize> for Segment<'_> {
+    type Output = Led;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.leds[index]
+    }
+}
+
+impl IndexMut<usize> for Segment<'_> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.leds[index]
+    }
+}
+
+impl<'a, 'b> IntoIterator for &'b Segment<'a> {
+    type Item = &'b Led;
+    type IntoIter = slice::Iter<'b, Led>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, 'b> IntoIterator for &'b mut Segment<'a> {
+    type Item = &'b mut Led;
+    type IntoIter = slice::IterMut<'b, Led>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_split() {
+        let mut leds = vec![Led::new(); 6];
+        let (mut left, mut right) = Segment::new(&mut leds).split_at(2);
+        assert_eq!(left.len(), 2);
+        assert_eq!(right.len(), 4);
+
+        left.fill(Led::from_rgb(255, 0, 0));
+        right.fill(Led::from_rgb(0, 0, 255));
+        right[0] = Led::from_rgb(0, 255, 0);
+        right.iter_mut().skip(2).for_each(|led| led.w = 10);
+
+        assert_eq!(leds[..2], [Led::from_rgb(255, 0, 0); 2]);
+        assert_eq!(leds[2], Led::from_rgb(0, 255, 0));
+        assert_eq!(leds[3], Led::from_rgb(0, 0, 255));
+        assert_eq!(leds[4..], [Led::from_rgbw(0, 0, 255, 10); 2]);
+    }
+
+    #[test]
+    fn test_segment_fill_gradient() {
+        let mut leds = vec![Led::new(); 3];
+        let mut segment = Segment::new(&mut leds);
+
+        segment.fill_gradient(
+            Led::from_rgbw(0, 0, 0, 0),
+            Led::from_rgbw(255, 255, 255, 200),
+        );
+        assert_eq!(segment[0], Led::new());
+        assert_eq!(segment[2], Led::from_rgbw(255, 255, 255, 200));
+        assert_eq!(segment[1].w, 100);
+
+        segment.clear();
+        assert!(segment.iter().all(|led| *led == Led::new()));
+    }
+
+    #[test]
+    fn test_segment_fill_gradient_stops() {
+        let mut leds = vec![Led::new(); 5];
+        let mut segment = Segment::new(&mut leds);
+        let stops = [
+            (0.0, Led::from_rgbw(255, 0, 0, 0)),
+            (0.5, Led::from_rgbw(0, 255, 0, 100)),
+            (1.0, Led::from_rgbw(0, 0, 255, 0)),
+        ];
+
+        segment.fill_gradient_stops(&stops);
+        assert_eq!(segment[0], stops[0].1);
+        assert_eq!(segment[1], Led::from_rgbw(128, 128, 0, 50));
+        assert_eq!(segment[2], stops[1].1);
+        assert_eq!(segment[3], Led::from_rgbw(0, 128, 128, 50));
+        assert_eq!(segment[4], stops[2].1);
+
+        segment.fill_gradient_stops(&[
+            (0.25, Led::from_rgb(10, 0, 0)),
+            (0.75, Led::from_rgb(30, 0, 0)),
+        ]);
+        assert_eq!(
+            segment.iter().map(|led| led.r).collect::<Vec<_>>(),
+            [10, 10, 20, 30, 30]
+        );
+    }
+
+    #[test]
+    fn test_segment_effect_helpers() {
+        let red = Led::from_rgb(255, 0, 0);
+        let mut leds = [Led::from_rgb(1, 2, 3); 6];
+        let (_, mut right) = Segment::new(&mut leds).split_at(2);
+
+        right.larson_scanner(1, 2, red);
+        right.fade_to_black_by(1);
+        assert_eq!(
+            right.iter().copied().collect::<Vec<_>>(),
+            [red * 0.5 - 1, red - 1, red * 0.5 - 1, Led::new()]
+        );
+        assert_eq!(leds[..2], [Led::from_rgb(1, 2, 3); 2]);
+    }
+}
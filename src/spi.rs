@@ -0,0 +1,120 @@
+use embedded_hal::spi::{self, ErrorKind, ErrorType, SpiBus};
+use rppal::spi::{Error as RppalError, Spi};
+use std::{error::Error, fmt, io};
+
+/// `rppal` SPI handle adapted to `embedded-hal` `SpiBus` trait.
+/// This is the default SPI backend used by `Strip` on RaspberryPi.
+#[derive(Debug)]
+pub struct RppalSpi(Spi);
+
+impl RppalSpi {
+    /// Wrap already configured `rppal` SPI handle
+    pub fn new(spi: Spi) -> Self {
+        Self(spi)
+    }
+
+    /// Get the wrapped `rppal` SPI handle back
+    pub fn into_inner(self) -> Spi {
+        self.0
+    }
+}
+
+/// Error returned by `RppalSpi`, wrapping `rppal` SPI error
+#[derive(Debug)]
+pub struct RppalSpiError(pub RppalError);
+
+impl RppalSpiError {
+    /// Check if the error was caused by SPI message exceeding kernel's SPI buffer size (`spidev.bufsiz`)
+    pub fn is_message_too_long(&self) -> bool {
+        match &self.0 {
+            RppalError::Io(io_error) => io_error.raw_os_error() == Some(libc::EMSGSIZE),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for RppalSpiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Error for RppalSpiError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+impl From<RppalError> for RppalSpiError {
+    fn from(error: RppalError) -> Self {
+        Self(error)
+    }
+}
+
+impl spi::Error for RppalSpiError {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+impl ErrorType for RppalSpi {
+    type Error = RppalSpiError;
+}
+
+// `SpiBus` has no notion of partial writes, so report them as errors
+fn check_written(written: usize, expected: usize) -> Result<(), RppalSpiError> {
+    if written < expected {
+        return Err(RppalError::Io(io::Error::new(
+            io::ErrorKind::WriteZero,
+            format!("only {} of {} bytes were written", written, expected),
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+impl SpiBus for RppalSpi {
+    fn read(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        self.0.read(words)?;
+        Ok(())
+    }
+
+    fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+        let written = self.0.write(words)?;
+        check_written(written, words.len())
+    }
+
+    fn transfer(&mut self, read: &mut [u8], write: &[u8]) -> Result<(), Self::Error> {
+        self.0.transfer(read, write)?;
+        Ok(())
+    }
+
+    fn transfer_in_place(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+        let write = words.to_vec();
+        self.0.transfer(words, &write)?;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        // rppal transfers are blocking, so there's nothing to flush
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_write_is_error() {
+        assert!(check_written(32, 32).is_ok());
+
+        let error = check_written(16, 32).unwrap_err();
+        assert!(matches!(
+            &error.0,
+            RppalError::Io(io_error) if io_error.kind() == io::ErrorKind::WriteZero
+        ));
+        assert_eq!(error.to_string(), "I/O error: only 16 of 32 bytes were written");
+        assert!(!error.is_message_too_long());
+    }
+}
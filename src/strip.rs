@@ -1,33 +1,359 @@
-use crate::led::Led;
-use palette::{Gradient, LinSrgb, Srgb};
+use crate::effect::Effect;
+use crate::frame::Frame;
+use crate::led::{ColorOrder, Led, PixelFormat};
+use crate::segment::{clamp_range, Segment};
+#[cfg(feature = "rppal")]
+use crate::spi::{RppalSpi, RppalSpiError};
+use embedded_hal::spi::SpiBus;
+use palette::{Gradient, LinSrgb};
+use rand::Rng;
+#[cfg(feature = "rppal")]
 pub use rppal::spi::{Bus, SlaveSelect};
-use rppal::spi::{Mode, Spi};
+#[cfg(feature = "rppal")]
+use rppal::spi::{Error as SpiError, Mode, Spi};
+#[cfg(feature = "metrics")]
+use std::collections::VecDeque;
+#[cfg(feature = "rppal")]
+use std::{any::Any, fs, path::Path};
 use std::{
     error::Error,
-    ops::{ShlAssign, ShrAssign},
-    thread,
-    time::Duration,
+    fmt, mem,
+    ops::{Index, IndexMut, Range, RangeBounds, ShlAssign, ShrAssign},
+    slice, thread,
+    time::{Duration, Instant},
 };
 
+#[cfg(feature = "rppal")]
 const SPI_FREQUENCY: u32 = 6_400_000;
+/// Current drawn by fully lit red element of a single LED, in milliamps
+pub const RED_CURRENT_MA: u32 = 20;
+/// Current drawn by fully lit green element of a single LED, in milliamps
+pub const GREEN_CURRENT_MA: u32 = 20;
+/// Current drawn by fully lit blue element of a single LED, in milliamps
+pub const BLUE_CURRENT_MA: u32 = 20;
+/// Current drawn by fully lit white element of a single LED, in milliamps
+pub const WHITE_CURRENT_MA: u32 = 20;
+/// Current drawn by a single LED with all elements turned off, in milliamps
+pub const QUIESCENT_CURRENT_MA: u32 = 1;
+
+/// Kernel parameter holding the SPI buffer size (`spidev.bufsiz`)
+#[cfg(feature = "rppal")]
+const SPIDEV_BUFSIZ_PATH: &str = "/sys/module/spidev/parameters/bufsiz";
+/// Default time required by the strip to latch the data after transmission
+const RESET_DURATION: Duration = Duration::from_micros(80);
+/// Default gamma used for correcting LED colors at transmit time
+const DEFAULT_GAMMA: f32 = 2.8;
+/// Amount of most recent updates included in `TimingStats`
+#[cfg(feature = "metrics")]
+const TIMING_WINDOW: usize = 64;
+
+/// Error returned when trying to access LED outside of the strip
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IndexError {
+    /// Index that was accessed
+    pub index: usize,
+    /// Amount of LEDs in the strip
+    pub len: usize,
+}
+
+impl fmt::Display for IndexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "LED index {} is out of range for strip with {} LEDs",
+            self.index, self.len
+        )
+    }
+}
+
+impl Error for IndexError {}
+
+/// Error returned when provided LED data doesn't match the length of the strip
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LengthError {
+    /// Amount of LEDs in the strip
+    pub expected: usize,
+    /// Amount of LEDs provided
+    pub actual: usize,
+}
+
+impl fmt::Display for LengthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "got {} LEDs for strip with {} LEDs",
+            self.actual, self.expected
+        )
+    }
+}
+
+impl Error for LengthError {}
+
+/// Error returned by `Strip` operations, generic over the error type of used SPI bus
+#[derive(Debug)]
+pub enum StripError<#[cfg(feature = "rppal")] E = RppalSpiError, #[cfg(not(feature = "rppal"))] E> {
+    /// SPI communication failed
+    Spi(E),
+    /// LED index was out of range
+    OutOfBounds(IndexError),
+    /// Provided LED data didn't match the length of the strip
+    LengthMismatch(LengthError),
+    /// SPI message was too long for the kernel SPI buffer. See `Strip::update` on how to increase it.
+    MessageTooLong(usize),
+    /// Encoded frame is larger than maximal frame size (see `Strip::set_max_frame_size`), which defaults to
+    /// kernel's `spidev.bufsiz` for RaspberryPi strips
+    FrameTooLarge {
+        /// Size of the encoded frame, in bytes
+        needed: usize,
+        /// Maximal allowed frame size, in bytes
+        max: usize,
+    },
+}
+
+impl<E: 'static> StripError<E> {
+    // Wrap the SPI error, detecting kernel's "message too long" error reported by rppal
+    #[cfg_attr(not(feature = "rppal"), allow(unused_variables))]
+    fn from_spi_error(error: E, message_length: usize) -> Self {
+        #[cfg(feature = "rppal")]
+        if let Some(rppal_error) = (&error as &dyn Any).downcast_ref::<RppalSpiError>() {
+            if rppal_error.is_message_too_long() {
+                return StripError::MessageTooLong(message_length);
+            }
+        }
+
+        StripError::Spi(error)
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for StripError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StripError::Spi(error) => write!(f, "SPI error: {}", error),
+            StripError::OutOfBounds(error) => error.fmt(f),
+            StripError::LengthMismatch(error) => error.fmt(f),
+            StripError::MessageTooLong(length) => write!(
+                f,
+                "SPI message of {} bytes is too long, increase spidev.bufsiz",
+                length
+            ),
+            StripError::FrameTooLarge { needed, max } => write!(
+                f,
+                "frame of {} bytes exceeds maximal frame size of {} bytes (set with Strip::set_max_frame_size or read from spidev.bufsiz)",
+                needed, max
+            ),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for StripError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StripError::Spi(error) => Some(error),
+            StripError::OutOfBounds(error) => Some(error),
+            StripError::LengthMismatch(error) => Some(error),
+            StripError::MessageTooLong(_) | StripError::FrameTooLarge { .. } => None,
+        }
+    }
+}
+
+#[cfg(feature = "rppal")]
+impl From<SpiError> for StripError {
+    fn from(error: SpiError) -> Self {
+        StripError::Spi(error.into())
+    }
+}
+
+impl<E> From<IndexError> for StripError<E> {
+    fn from(error: IndexError) -> Self {
+        StripError::OutOfBounds(error)
+    }
+}
+
+impl<E> From<LengthError> for StripError<E> {
+    fn from(error: LengthError) -> Self {
+        StripError::LengthMismatch(error)
+    }
+}
+
+/// Time spent in `update` (encoding and SPI transfer, without waiting for the latch) over the most recent updates.
+/// All durations are zero if there were no updates yet.
+#[cfg(feature = "metrics")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TimingStats {
+    /// Shortest update
+    pub min: Duration,
+    /// Longest update
+    pub max: Duration,
+    /// Average update time
+    pub average: Duration,
+    /// Amount of updates the statistics were computed from
+    pub samples: usize,
+}
 
 /// Structure representing whole SK6812RGBW strip.
 /// Should be compatible with other similar LED's, but they would likely require a different bit ordering
+///
+/// Strip is generic over `embedded-hal` SPI bus used to send the data. By default (with `rppal` feature)
+/// it uses RaspberryPi SPI via `rppal` library.
 #[derive(Debug)]
-pub struct Strip {
-    spi: Spi,
+pub struct Strip<#[cfg(feature = "rppal")] SPI = RppalSpi, #[cfg(not(feature = "rppal"))] SPI> {
+    spi: SPI,
     pub leds: Vec<Led>,
+    gamma: [f32; 4],
+    gamma_tables: [[u8; 256]; 4],
+    color_order: ColorOrder,
+    brightness: u8,
+    rgb_brightness: u8,
+    white_brightness: u8,
+    white_balance: [f32; 3],
+    heat: Vec<u8>,
+    pixel_format: PixelFormat,
+    dithering: bool,
+    dither_error: Vec<[f32; 4]>,
+    max_frame_size: Option<usize>,
+    max_transfer_size: Option<usize>,
+    reset_delay: Duration,
+    standby: bool,
+    #[cfg(feature = "metrics")]
+    update_times: VecDeque<Duration>,
+    back_buffer: Vec<Led>,
+    buffer: Vec<u8>,
+    ready_at: Instant,
+}
+
+/// Builder for `Strip` with non-default configuration.
+/// Options that are not set keep the same defaults as in `Strip::new`.
+#[derive(Clone, Debug)]
+pub struct StripBuilder {
+    #[cfg(feature = "rppal")]
+    bus: Bus,
+    #[cfg(feature = "rppal")]
+    slave_select: SlaveSelect,
+    #[cfg(feature = "rppal")]
+    frequency: u32,
+    amount_of_leds: usize,
+    color_order: ColorOrder,
+    pixel_format: PixelFormat,
+    gamma: f32,
+    brightness: u8,
+}
+
+impl StripBuilder {
+    /// Create new builder for a strip without LEDs, on SPI0 bus with default settings
+    pub fn new() -> Self {
+        Self {
+            #[cfg(feature = "rppal")]
+            bus: Bus::Spi0,
+            #[cfg(feature = "rppal")]
+            slave_select: SlaveSelect::Ss0,
+            #[cfg(feature = "rppal")]
+            frequency: SPI_FREQUENCY,
+            amount_of_leds: 0,
+            color_order: ColorOrder::default(),
+            pixel_format: PixelFormat::default(),
+            gamma: DEFAULT_GAMMA,
+            brightness: u8::MAX,
+        }
+    }
+
+    /// Set the SPI bus the strip is connected to
+    #[cfg(feature = "rppal")]
+    pub fn bus(mut self, bus: Bus) -> Self {
+        self.bus = bus;
+        self
+    }
+
+    /// Set the slave-select pin used to initialize SPI, see `Strip::new_with_custom_ss`
+    #[cfg(feature = "rppal")]
+    pub fn slave_select(mut self, slave_select: SlaveSelect) -> Self {
+        self.slave_select = slave_select;
+        self
+    }
+
+    /// Set SPI clock frequency (in Hz), see `Strip::new_with_frequency` for valid range
+    #[cfg(feature = "rppal")]
+    pub fn frequency(mut self, frequency: u32) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    /// Set the amount of LEDs in the strip
+    pub fn leds(mut self, amount_of_leds: usize) -> Self {
+        self.amount_of_leds = amount_of_leds;
+        self
+    }
+
+    /// Set the color channel order, see `Strip::set_color_order`
+    pub fn color_order(mut self, color_order: ColorOrder) -> Self {
+        self.color_order = color_order;
+        self
+    }
+
+    /// Set the format of data sent to the strip, see `Strip::set_pixel_format`
+    pub fn pixel_format(mut self, pixel_format: PixelFormat) -> Self {
+        self.pixel_format = pixel_format;
+        self
+    }
+
+    /// Set the gamma correction, see `Strip::set_gamma`
+    pub fn gamma(mut self, gamma: f32) -> Self {
+        self.gamma = gamma;
+        self
+    }
+
+    /// Set the global brightness, see `Strip::set_brightness`
+    pub fn brightness(mut self, brightness: u8) -> Self {
+        self.brightness = brightness;
+        self
+    }
+
+    /// Create the strip on configured RaspberryPi SPI bus
+    #[cfg(feature = "rppal")]
+    pub fn build(self) -> Result<Strip, StripError> {
+        let strip = Strip::new_with_custom_ss_and_frequency(
+            self.bus,
+            self.amount_of_leds,
+            self.slave_select,
+            self.frequency,
+        )?;
+        Ok(self.configure(strip))
+    }
+
+    /// Create the strip driven by any `embedded-hal` SPI bus. Bus, slave-select and frequency options are ignored,
+    /// since the bus should already be configured.
+    pub fn build_with_spi_bus<SPI>(self, spi: SPI) -> Strip<SPI> {
+        let strip = Strip::from_spi_bus(spi, self.amount_of_leds);
+        self.configure(strip)
+    }
+
+    // Apply configured options to the strip
+    fn configure<SPI>(&self, mut strip: Strip<SPI>) -> Strip<SPI> {
+        strip.set_color_order(self.color_order);
+        strip.set_pixel_format(self.pixel_format);
+        strip.set_gamma(self.gamma);
+        strip.set_brightness(self.brightness);
+        strip
+    }
 }
 
-impl Strip {
+impl Default for StripBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "rppal")]
+impl Strip<RppalSpi> {
     /// Create new SK6812RGBW strip
     /// Since rppal library requires slave-select pin to initalize SPI, by default SS0 is selected. It's not used to drive LEDs, so it's a wasted pin.
     /// If you want to select other pin, use `new_with_custom_ss` method.
-    pub fn new(bus: Bus, amount_of_leds: usize) -> Result<Self, Box<dyn Error>> {
-        Ok(Self {
-            spi: Spi::new(bus, SlaveSelect::Ss0, SPI_FREQUENCY, Mode::Mode0)?,
-            leds: vec![Led::new(); amount_of_leds],
-        })
+    pub fn new(bus: Bus, amount_of_leds: usize) -> Result<Self, StripError> {
+        Self::new_with_frequency(bus, amount_of_leds, SPI_FREQUENCY)
+    }
+
+    /// Get a builder for strip with non-default configuration
+    pub fn builder() -> StripBuilder {
+        StripBuilder::new()
     }
 
     /// Create new SK6812RGBW strip with custom slave-select pin
@@ -36,11 +362,225 @@ impl Strip {
         bus: Bus,
         amount_of_leds: usize,
         slave_select: SlaveSelect,
-    ) -> Result<Self, Box<dyn Error>> {
-        Ok(Self {
-            spi: Spi::new(bus, slave_select, SPI_FREQUENCY, Mode::Mode0)?,
+    ) -> Result<Self, StripError> {
+        Self::new_with_custom_ss_and_frequency(bus, amount_of_leds, slave_select, SPI_FREQUENCY)
+    }
+
+    /// Create new SK6812RGBW strip with custom SPI clock frequency (in Hz). Default is 6.4MHz.
+    /// Every bit sent to the strip is encoded as a single SPI byte, so the frequency should be about 8 times
+    /// higher than the strip's data rate. SK6812 expects bit period of 1.25µs (±~150ns), which gives a valid
+    /// range of roughly 5.8MHz to 7.2MHz. Frequencies outside of it will most likely result in garbage colors.
+    pub fn new_with_frequency(
+        bus: Bus,
+        amount_of_leds: usize,
+        frequency: u32,
+    ) -> Result<Self, StripError> {
+        Self::new_with_custom_ss_and_frequency(bus, amount_of_leds, SlaveSelect::Ss0, frequency)
+    }
+
+    /// Create new SK6812RGBW strip with custom slave-select pin and SPI clock frequency (in Hz).
+    /// See `new_with_frequency` for details about valid frequency range.
+    pub fn new_with_custom_ss_and_frequency(
+        bus: Bus,
+        amount_of_leds: usize,
+        slave_select: SlaveSelect,
+        frequency: u32,
+    ) -> Result<Self, StripError> {
+        let spi = Spi::new(bus, slave_select, frequency, Mode::Mode0)?;
+        Ok(Self::from_spi(spi, amount_of_leds))
+    }
+
+    /// Create new SK6812RGBW strip using already opened `rppal` SPI handle, for full control over its configuration.
+    /// The SPI should use mode 0 and frequency of about 6.4MHz, see `new_with_frequency` for details.
+    pub fn from_spi(spi: Spi, amount_of_leds: usize) -> Self {
+        let mut strip = Self::from_spi_bus(RppalSpi::new(spi), amount_of_leds);
+        strip.set_max_frame_size(read_spi_buffer_size(SPIDEV_BUFSIZ_PATH));
+        strip
+    }
+}
+
+impl<SPI> Strip<SPI> {
+    /// Create new SK6812RGBW strip driven by any `embedded-hal` SPI bus.
+    /// The bus should be configured to the frequency of about 6.4MHz, see `new_with_frequency` for details.
+    pub fn from_spi_bus(spi: SPI, amount_of_leds: usize) -> Self {
+        Self {
+            spi,
             leds: vec![Led::new(); amount_of_leds],
-        })
+            gamma: [DEFAULT_GAMMA; 4],
+            gamma_tables: [gamma_table(DEFAULT_GAMMA); 4],
+            color_order: ColorOrder::default(),
+            brightness: u8::MAX,
+            rgb_brightness: u8::MAX,
+            white_brightness: u8::MAX,
+            white_balance: [1.0; 3],
+            heat: Vec::new(),
+            pixel_format: PixelFormat::default(),
+            dithering: false,
+            dither_error: Vec::new(),
+            max_frame_size: None,
+            max_transfer_size: None,
+            reset_delay: RESET_DURATION,
+            standby: false,
+            #[cfg(feature = "metrics")]
+            update_times: VecDeque::with_capacity(TIMING_WINDOW),
+            back_buffer: vec![Led::new(); amount_of_leds],
+            buffer: Vec::new(),
+            ready_at: Instant::now(),
+        }
+    }
+
+    // Get the SPI bus used by the strip
+    #[cfg(any(test, feature = "mock"))]
+    pub(crate) fn spi(&self) -> &SPI {
+        &self.spi
+    }
+
+    /// Get the amount of LEDs in the strip
+    pub fn len(&self) -> usize {
+        self.leds.len()
+    }
+
+    /// Check if the strip has no LEDs
+    pub fn is_empty(&self) -> bool {
+        self.leds.is_empty()
+    }
+
+    /// Change the amount of LEDs in the strip, keeping the colors of existing ones.
+    /// New LEDs are turned off, LEDs past `new_len` are removed. Internal buffers are resized accordingly.
+    pub fn resize(&mut self, new_len: usize) {
+        self.leds.resize(new_len, Led::new());
+        self.back_buffer.resize(new_len, Led::new());
+        self.heat.resize(new_len, 0);
+        self.buffer.clear();
+        self.buffer
+            .shrink_to(new_len * self.pixel_format.raw_bytes_per_led());
+    }
+
+    /// Append LEDs of `other` strip to this one, for strips physically chained on the same data line.
+    /// The result is addressed as a single strip, where LED `self.len()` is the first LED of `other`,
+    /// and is sent as one continuous transfer via this strip's SPI bus. SPI bus of `other` is dropped.
+    pub fn chain<OtherSPI>(mut self, other: Strip<OtherSPI>) -> Self {
+        let len = self.len();
+        self.resize(len + other.len());
+        self.leds[len..].copy_from_slice(&other.leds);
+        self
+    }
+
+    /// Iterate over the LEDs of the strip
+    pub fn iter(&self) -> slice::Iter<'_, Led> {
+        self.leds.iter()
+    }
+
+    /// Iterate over mutable references to the LEDs of the strip
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, Led> {
+        self.leds.iter_mut()
+    }
+
+    /// Call `f` for every LED of the strip, together with its index
+    pub fn for_each_indexed(&mut self, mut f: impl FnMut(usize, &mut Led)) {
+        self.leds
+            .iter_mut()
+            .enumerate()
+            .for_each(|(index, led)| f(index, led));
+    }
+
+    /// Get the color of LED at `index`, or `None` if it's out of range
+    pub fn get(&self, index: usize) -> Option<Led> {
+        self.leds.get(index).copied()
+    }
+
+    /// Set the color of LED at `index`. Returns an error if the index is out of range, instead of panicking.
+    pub fn set(&mut self, index: usize, led: Led) -> Result<(), IndexError> {
+        let len = self.leds.len();
+        let strip_led = self.leds.get_mut(index).ok_or(IndexError { index, len })?;
+        *strip_led = led;
+        Ok(())
+    }
+
+    /// Set colors of successive LEDs from `iter`, starting at the beginning of the strip.
+    /// Stops at the end of the strip or the iterator, whichever is shorter, leaving the remaining LEDs untouched.
+    pub fn set_from_iter(&mut self, iter: impl IntoIterator<Item = Led>) {
+        self.leds
+            .iter_mut()
+            .zip(iter)
+            .for_each(|(strip_led, led)| *strip_led = led);
+    }
+
+    /// Get the back buffer, which can be used to compose the next frame without modifying `leds`.
+    /// Changes made to it become visible after calling `swap_buffers`.
+    pub fn back(&mut self) -> &mut [Led] {
+        self.back_buffer.resize(self.leds.len(), Led::new());
+        &mut self.back_buffer
+    }
+
+    /// Swap the back buffer with `leds`, making the composed frame the one sent by `update`.
+    /// The back buffer then contains the previous frame.
+    pub fn swap_buffers(&mut self) {
+        self.back_buffer.resize(self.leds.len(), Led::new());
+        mem::swap(&mut self.leds, &mut self.back_buffer);
+    }
+
+    /// Copy the whole frame from `src` into the strip. Returns an error if `src` length is different than the strip's.
+    pub fn copy_from_slice(&mut self, src: &[Led]) -> Result<(), LengthError> {
+        if src.len() != self.leds.len() {
+            return Err(LengthError {
+                expected: self.leds.len(),
+                actual: src.len(),
+            });
+        }
+
+        self.leds.copy_from_slice(src);
+        Ok(())
+    }
+
+    /// Save current colors of all LEDs, to restore them later with `restore`
+    pub fn snapshot(&self) -> Vec<Led> {
+        self.leds.clone()
+    }
+
+    /// Restore colors saved with `snapshot`. Snapshot length must match the strip length.
+    pub fn restore(&mut self, snapshot: &[Led]) -> Result<(), LengthError> {
+        self.copy_from_slice(snapshot)
+    }
+
+    /// Copy LED colors from a frame rendered independently of the strip. Frame length must match the strip length.
+    pub fn write_frame(&mut self, frame: &Frame) -> Result<(), LengthError> {
+        self.copy_from_slice(&frame.leds)
+    }
+
+    /// Blend `overlay` over current colors of the strip with global opacity `alpha` (clamped to 0.0..=1.0),
+    /// interpolating every channel. Alpha of 0.0 leaves the strip unchanged, 1.0 replaces it with `overlay`.
+    /// Overlay length must match the strip length.
+    pub fn composite(&mut self, overlay: &[Led], alpha: f32) -> Result<(), LengthError> {
+        if overlay.len() != self.leds.len() {
+            return Err(LengthError {
+                expected: self.leds.len(),
+                actual: overlay.len(),
+            });
+        }
+
+        self.leds
+            .iter_mut()
+            .zip(overlay)
+            .for_each(|(led, &top)| *led = led.lerp(top, alpha));
+        Ok(())
+    }
+
+    /// Copy as many LEDs from `src` as fit in the strip, starting from the first one. Returns the amount of copied LEDs.
+    pub fn copy_from_slice_clamped(&mut self, src: &[Led]) -> usize {
+        let len = src.len().min(self.leds.len());
+        self.leds[..len].copy_from_slice(&src[..len]);
+        len
+    }
+
+    /// Check if all LEDs in the strip are turned off
+    pub fn is_all_off(&self) -> bool {
+        self.leds.iter().all(Led::is_off)
+    }
+
+    /// Get the amount of LEDs that are not turned off
+    pub fn count_lit(&self) -> usize {
+        self.leds.iter().filter(|led| !led.is_off()).count()
     }
 
     /// Set the color of all LEDs in the strip at once
@@ -48,6 +588,119 @@ impl Strip {
         self.leds.fill(led);
     }
 
+    /// Set the color of LEDs in specified range, for example `2..5`, `2..=4` or `..`.
+    /// Parts of the range outside of the strip are ignored.
+    pub fn fill_range(&mut self, range: impl RangeBounds<usize>, led: Led) {
+        Segment::new(&mut self.leds).fill_range(range, led);
+    }
+
+    /// Get a mutable view of LEDs in `range`, which can be filled independently from the rest of the strip.
+    /// Parts of the range outside of the strip are ignored.
+    pub fn segment(&mut self, range: Range<usize>) -> Segment<'_> {
+        let range = clamp_range(range, self.leds.len());
+        Segment::new(&mut self.leds[range])
+    }
+
+    /// Scale HSV value (brightness) of every LED by `value`, keeping its hue and saturation.
+    /// White channel is not modified. Colors stored in `leds` are changed, unlike with `set_brightness`.
+    pub fn set_value(&mut self, value: f32) {
+        Segment::new(&mut self.leds).set_value(value);
+    }
+
+    /// Display `level` (clamped to 0.0..=1.0) as a bar of lit LEDs starting at the beginning of the strip, turning off the rest.
+    /// Color of every LED depends on its position, changing from `low` on the first LED to `high` on the last one.
+    /// The LED at the end of the bar is partially lit, proportionally to the fractional part of the level.
+    pub fn vu_meter(&mut self, level: f32, low: Led, high: Led) {
+        Segment::new(&mut self.leds).vu_meter(level, low, high);
+    }
+
+    /// Dim all LEDs by subtracting `amount` from every channel, saturating at 0.
+    /// Call it every frame before drawing new pixels to get trailing decay effects.
+    pub fn fade_to_black_by(&mut self, amount: u8) {
+        Segment::new(&mut self.leds).fade_to_black_by(amount);
+    }
+
+    /// Fill the strip with `color` when `on` is true, turn it off otherwise.
+    /// Toggle `on` every interval to get a blinking indicator.
+    pub fn blink(&mut self, color: Led, on: bool) {
+        Segment::new(&mut self.leds).blink(color, on);
+    }
+
+    /// Same as `blink`, but only for LEDs in specified range. LEDs outside of the range are not modified.
+    pub fn blink_range(&mut self, range: impl RangeBounds<usize>, color: Led, on: bool) {
+        Segment::new(&mut self.leds).blink_range(range, color, on);
+    }
+
+    /// Fill the strip with `base` color scaled by sinusoidal brightness `(sin(phase) + 1) / 2`.
+    /// Advance `phase` over time to get a breathing effect.
+    pub fn apply_breathing(&mut self, base: Led, phase: f32) {
+        Segment::new(&mut self.leds).apply_breathing(base, phase);
+    }
+
+    /// Light every `spacing`-th LED with `color` and turn off the rest. LED `i` is lit when `(i + offset) % spacing == 0`.
+    /// Increment `offset` every frame to get a theater chase (marquee) effect. `spacing` of 0 is treated as 1.
+    pub fn theater_chase(&mut self, color: Led, spacing: usize, offset: usize) {
+        Segment::new(&mut self.leds).theater_chase(color, spacing, offset);
+    }
+
+    /// Draw a comet with full `color` at `head` index and a fading tail of `tail_len` LEDs behind it (at lower indexes).
+    /// Each next tail LED loses `decay` (0.0..=1.0) part of the previous one's brightness. All other LEDs are turned off.
+    /// `head` can be outside of the strip, in which case only the visible part of the tail is drawn.
+    pub fn comet(&mut self, head: usize, color: Led, tail_len: usize, decay: f32) {
+        Segment::new(&mut self.leds).comet(head, color, tail_len, decay);
+    }
+
+    /// Draw a symmetric glowing block with full `color` at `center` index, fading out linearly over `width` LEDs on both sides.
+    /// LED at distance `d` from the center gets `1 - d / width` of the color brightness. All other LEDs are turned off.
+    /// Move `center` back and forth every frame to get the Larson scanner effect. Parts outside of the strip are not drawn.
+    pub fn larson_scanner(&mut self, center: usize, width: usize, color: Led) {
+        Segment::new(&mut self.leds).larson_scanner(center, width, color);
+    }
+
+    /// Light a random `density` (0.0..=1.0) fraction of LEDs with `color`, leaving the rest untouched.
+    /// Randomness comes from provided `rng`, so the effect is deterministic for seeded generators.
+    pub fn twinkle(&mut self, color: Led, density: f32, rng: &mut impl Rng) {
+        Segment::new(&mut self.leds).twinkle(color, density, rng);
+    }
+
+    /// Set all LEDs up to and including `position` to `color` and turn off the rest.
+    /// Increment `position` every frame to get a color wipe effect. Positions past the end light the whole strip.
+    pub fn color_wipe_step(&mut self, color: Led, position: usize) {
+        Segment::new(&mut self.leds).color_wipe_step(color, position);
+    }
+
+    /// Simulate a single frame of fire, rising from the beginning of the strip (Fire2012 algorithm).
+    /// Every cell of the heat map is cooled down by up to `cooling`, then heat diffuses towards the end of the strip,
+    /// and with `sparking`/255 chance a new spark is ignited near the beginning. Heat is then mapped to black-red-yellow-white colors.
+    /// The heat map is kept inside the strip between frames.
+    pub fn fire_step(&mut self, cooling: u8, sparking: u8, rng: &mut impl Rng) {
+        let len = self.leds.len();
+        if len == 0 {
+            return;
+        }
+        self.heat.resize(len, 0);
+
+        let max_cooling = (cooling as usize * 10 / len + 2).min(u8::MAX as usize) as u8;
+        self.heat
+            .iter_mut()
+            .for_each(|cell| *cell = cell.saturating_sub(rng.gen_range(0..=max_cooling)));
+
+        for index in (2..len).rev() {
+            self.heat[index] =
+                ((self.heat[index - 1] as u16 + 2 * self.heat[index - 2] as u16) / 3) as u8;
+        }
+
+        if rng.gen::<u8>() < sparking {
+            let index = rng.gen_range(0..len.min(7));
+            self.heat[index] = self.heat[index].saturating_add(rng.gen_range(160..=255));
+        }
+
+        self.leds
+            .iter_mut()
+            .zip(&self.heat)
+            .for_each(|(led, heat)| *led = heat_color(*heat));
+    }
+
     // Turn off all the LEDs
     pub fn clear(&mut self) {
         self.leds.fill(Led::new());
@@ -55,12 +708,73 @@ impl Strip {
 
     // Set the strip LED colors to a `palette` gradient
     pub fn set_gradient(&mut self, gradient: Gradient<LinSrgb>) {
-        gradient
-            .take(self.leds.len())
-            .zip(&mut self.leds)
-            .for_each(|(color, led)| {
-                *led = Srgb::from_linear(color).into();
-            });
+        Segment::new(&mut self.leds).set_gradient(gradient);
+    }
+
+    /// Set the strip LED colors to a `palette` gradient, with gamma correction applied to every color before storing it.
+    /// Use it to get perceptually smooth brightness ramps, as LED PWM output is linear. See `set_gamma` for transmit-time correction.
+    pub fn set_gradient_gamma(&mut self, gradient: Gradient<LinSrgb>, gamma: f32) {
+        Segment::new(&mut self.leds).set_gradient_gamma(gradient, gamma);
+    }
+
+    /// Fill the strip with a gradient from `start` color on the first LED to `end` color on the last one.
+    /// RGB channels are interpolated in linear RGB space, white channel is interpolated separately.
+    pub fn fill_gradient(&mut self, start: Led, end: Led) {
+        Segment::new(&mut self.leds).fill_gradient(start, end);
+    }
+
+    /// Fill the strip with a gradient defined by `(position, color)` stops, with positions in 0.0..=1.0 range sorted in ascending order.
+    /// See `Segment::fill_gradient_stops` for details.
+    pub fn fill_gradient_stops(&mut self, stops: &[(f32, Led)]) {
+        Segment::new(&mut self.leds).fill_gradient_stops(stops);
+    }
+
+    /// Fill the strip with a rainbow, starting from `start_hue` (in degrees) and changing the hue by `hue_step` degrees for each next LED.
+    /// Colors have full saturation and value, hue wraps around after 360 degrees.
+    pub fn fill_rainbow(&mut self, start_hue: f32, hue_step: f32) {
+        Segment::new(&mut self.leds).fill_rainbow(start_hue, hue_step);
+    }
+
+    /// Fill the strip with a rainbow spanning `hue_span` degrees, starting from `phase` (in degrees) on the first LED.
+    /// Advance `phase` every frame to scroll the rainbow. Colors are recomputed on every call, so there's no rounding drift.
+    pub fn animate_rainbow(&mut self, phase: f32, hue_span: f32) {
+        Segment::new(&mut self.leds).animate_rainbow(phase, hue_span);
+    }
+
+    /// Fill the strip with colors sampled from `palette` treated as a looping gradient, interpolating between entries.
+    /// The whole palette spans the strip once, `phase` (in palette entries) shifts it - advance it every frame to scroll.
+    /// Empty palette turns the strip off.
+    pub fn cycle_palette(&mut self, palette: &[Led], phase: f32) {
+        Segment::new(&mut self.leds).cycle_palette(palette, phase);
+    }
+
+    /// Fill the strip with a plasma effect - hue of every LED is computed from a sum of sine waves of its position and `time`.
+    /// Advance `time` every frame to animate it. `scale` sets the spatial frequency, higher values give more color bands.
+    pub fn plasma_step(&mut self, time: f32, scale: f32) {
+        Segment::new(&mut self.leds).plasma_step(time, scale);
+    }
+
+    /// Reflect the first half of the strip onto the second half, so LED `i` is copied to LED `len - 1 - i`.
+    /// For odd amount of LEDs, the middle one is left untouched.
+    pub fn mirror(&mut self) {
+        Segment::new(&mut self.leds).mirror();
+    }
+
+    /// Reverse the order of LEDs in the strip
+    pub fn reverse(&mut self) {
+        self.leds.reverse();
+    }
+
+    /// Move LEDs `count` positions towards the beginning of the strip, without wrapping.
+    /// LEDs shifted past the beginning are dropped and vacated LEDs at the end are set to `fill`.
+    pub fn scroll_left(&mut self, count: usize, fill: Led) {
+        Segment::new(&mut self.leds).scroll_left(count, fill);
+    }
+
+    /// Move LEDs `count` positions towards the end of the strip, without wrapping.
+    /// LEDs shifted past the end are dropped and vacated LEDs at the beginning are set to `fill`.
+    pub fn scroll_right(&mut self, count: usize, fill: Led) {
+        Segment::new(&mut self.leds).scroll_right(count, fill);
     }
 
     // "Rotate" LEDs to the beginning of the strip - move the first LED color to the end, 2nd to the first, and so on.
@@ -75,99 +789,1575 @@ impl Strip {
         self.leds.rotate_right(count);
     }
 
-    /// Call this to send the data from `leds` to the strip
-    /// This function will block the thread for ~80us after sending the data,
-    /// which is caused by strip comms protocol requirements.
-    ///
-    /// If you're getting an error, telling you that the message is too long - increase the SPI transfer size in `/boot/cmdline.txt`.
-    /// To do so, add `spidev.bufsiz=65535` to the first line of this file. I added it right before `rootwait`, but placement shouldn't matter.
-    pub fn update(&mut self) -> Result<(), Box<dyn Error>> {
-        let led_data: Vec<u8> = self.raw_led_data().collect();
-        self.spi.write(&led_data)?;
-        thread::sleep(Duration::from_micros(80));
+    /// Set the gamma correction applied to LED colors when sending them to the strip.
+    /// Default gamma is 2.8, use 1.0 to disable the correction.
+    /// Colors stored in `leds` are not modified, the correction is applied only at transmit time.
+    pub fn set_gamma(&mut self, gamma: f32) {
+        self.set_gamma_per_channel(gamma, gamma, gamma, gamma);
+    }
 
-        Ok(())
+    /// Get the currently used gamma correction value. If gamma is set per channel, this is the gamma of red channel.
+    pub fn gamma(&self) -> f32 {
+        self.gamma[0]
     }
 
-    // Get raw LED bytes, ready to send via SPI. This function is called internally by the library and usually you don't need to worry about it.
-    fn raw_led_data(&self) -> impl Iterator<Item = u8> + '_ {
-        self.leds.iter().flat_map(|led| led.to_raw_led_bytes())
+    /// Set separate gamma correction for every channel, to compensate for different efficiency curves of color elements.
+    /// Works like `set_gamma`, which sets the same value for all channels.
+    pub fn set_gamma_per_channel(&mut self, r: f32, g: f32, b: f32, w: f32) {
+        self.gamma = [r, g, b, w];
+        self.gamma_tables = self.gamma.map(gamma_table);
     }
-}
 
-impl ShrAssign<usize> for Strip {
-    fn shr_assign(&mut self, rhs: usize) {
-        self.shift_right(rhs);
+    /// Get gamma correction values of red, green, blue and white channels
+    pub fn gamma_per_channel(&self) -> [f32; 4] {
+        self.gamma
     }
-}
 
-impl ShlAssign<usize> for Strip {
-    fn shl_assign(&mut self, rhs: usize) {
-        self.shift_left(rhs);
+    /// Set the order in which color channels are sent to the strip. Default is GRBW.
+    pub fn set_color_order(&mut self, color_order: ColorOrder) {
+        self.color_order = color_order;
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Get the currently used color channel order
+    pub fn color_order(&self) -> ColorOrder {
+        self.color_order
+    }
 
-    fn make_strip() -> Strip {
-        Strip::new(Bus::Spi0, 144).unwrap()
+    /// Set the gains applied to red, green and blue channels when sending the data to the strip.
+    /// Use it to match the color temperature of RGB-mixed white with the white LED, for example
+    /// lowering blue gain makes RGB whites warmer. White channel is not affected.
+    /// Default gains are 1.0, which leave the colors unchanged. Colors stored in `leds` are not modified.
+    pub fn set_white_balance(&mut self, r_gain: f32, g_gain: f32, b_gain: f32) {
+        self.white_balance = [r_gain, g_gain, b_gain];
     }
 
-    #[test]
-    fn test_setting_color() {
-        let led: Led = [100, 0, 0].into();
-        let mut strip = make_strip();
+    /// Get the current white balance gains for red, green and blue channels
+    pub fn white_balance(&self) -> [f32; 3] {
+        self.white_balance
+    }
 
-        strip.fill(led);
+    /// Set the format of data sent to the strip. Default is RGBW, use RGB for strips without white channel (like WS2812).
+    /// In RGB mode, white channel of `Led` is ignored.
+    pub fn set_pixel_format(&mut self, pixel_format: PixelFormat) {
+        self.pixel_format = pixel_format;
+    }
 
-        strip.leds.iter().for_each(|strip_led| {
-            assert_eq!(*strip_led, led);
-        })
+    /// Get the currently used pixel format
+    pub fn pixel_format(&self) -> PixelFormat {
+        self.pixel_format
     }
 
-    #[test]
-    fn test_clearing() {
-        let led: Led = [100, 0, 0].into();
-        let mut strip = make_strip();
+    /// Set the global brightness of the strip, where 255 is full brightness and 0 turns all the LEDs off.
+    /// Every channel is scaled by `brightness / 255` when sending the data to the strip,
+    /// colors stored in `leds` are not modified.
+    pub fn set_brightness(&mut self, brightness: u8) {
+        self.brightness = brightness;
+    }
 
-        strip.fill(led);
-        strip.clear();
+    /// Get the current global brightness of the strip
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
 
-        strip.leds.iter().for_each(|strip_led| {
-            assert_eq!(*strip_led, Led::new());
-        })
+    /// Set the brightness of RGB channels, applied at transmit time on top of the global brightness.
+    /// Use it together with `set_white_brightness` to balance RGB and white LEDs. Default is 255.
+    pub fn set_rgb_brightness(&mut self, brightness: u8) {
+        self.rgb_brightness = brightness;
     }
 
-    #[test]
-    fn test_shift_right() {
-        let mut strip = Strip::new(Bus::Spi0, 5).unwrap();
+    /// Get the current brightness of RGB channels
+    pub fn rgb_brightness(&self) -> u8 {
+        self.rgb_brightness
+    }
 
-        strip.leds[0].r = 1;
-        strip.leds[1].r = 2;
-        strip.leds[2].r = 3;
-        strip.leds[3].r = 4;
-        strip.leds[4].r = 5;
+    /// Set the brightness of white channel, applied at transmit time on top of the global brightness. Default is 255.
+    pub fn set_white_brightness(&mut self, brightness: u8) {
+        self.white_brightness = brightness;
+    }
 
-        strip >>= 1;
+    /// Get the current brightness of white channel
+    pub fn white_brightness(&self) -> u8 {
+        self.white_brightness
+    }
 
-        assert_eq!(strip.leds[0].r, 5);
-        assert_eq!(strip.leds[1].r, 1);
-        assert_eq!(strip.leds[2].r, 2);
-        assert_eq!(strip.leds[3].r, 3);
-        assert_eq!(strip.leds[4].r, 4);
+    /// Enable or disable temporal dithering. When enabled, fractional channel values resulting from brightness scaling
+    /// are alternately rounded up and down between successive updates, so the average output approximates them.
+    /// Improves smoothness at low brightness levels, but requires the strip to be updated frequently.
+    pub fn enable_dithering(&mut self, enabled: bool) {
+        self.dithering = enabled;
+        self.dither_error.clear();
     }
 
-    #[test]
-    fn test_shift_left() {
-        let mut strip = Strip::new(Bus::Spi0, 5).unwrap();
+    /// Check if temporal dithering is enabled
+    pub fn is_dithering_enabled(&self) -> bool {
+        self.dithering
+    }
 
-        strip.leds[0].r = 1;
-        strip.leds[1].r = 2;
-        strip.leds[2].r = 3;
-        strip.leds[3].r = 4;
-        strip.leds[4].r = 5;
+    /// Set maximal size (in bytes) of the frame sent via SPI. Larger frames are rejected by `update` with `StripError::FrameTooLarge`,
+    /// instead of failing with a kernel error. Strips created with `Strip::new` or `Strip::from_spi` default to kernel's
+    /// `spidev.bufsiz` (if it can be read), use `None` to disable the check. Every LED takes 32 bytes (24 in RGB mode).
+    pub fn set_max_frame_size(&mut self, max_frame_size: Option<usize>) {
+        self.max_frame_size = max_frame_size;
+    }
+
+    /// Get maximal size (in bytes) of the frame sent via SPI, if it's limited
+    pub fn max_frame_size(&self) -> Option<usize> {
+        self.max_frame_size
+    }
+
+    /// Set maximal size (in bytes) of a single SPI transfer, enabling splitting of larger frames by `update` into multiple
+    /// transfers at LED boundaries. Splitting is disabled (`None`) by default, and frames above `max_frame_size` are still rejected.
+    ///
+    /// Every chunk is a separate SPI transfer, and if the gap between them reaches the reset time of the strip (50-80µs),
+    /// the strip latches in the middle of the frame and the rest of it is displayed from the first LED, corrupting the frame.
+    /// Prefer increasing `spidev.bufsiz` (see `update`), and use splitting only if that's not possible.
+    pub fn set_max_transfer_size(&mut self, max_transfer_size: Option<usize>) {
+        self.max_transfer_size = max_transfer_size;
+    }
+
+    /// Get maximal size (in bytes) of a single SPI transfer supported by the bus, if it's known
+    pub fn max_transfer_size(&self) -> Option<usize> {
+        self.max_transfer_size
+    }
+
+    /// Set the time required by the strip to latch the data after transmission, which `update` waits for.
+    /// Default is 80µs, which should work for most SK6812 strips. Some clones may need longer delay.
+    pub fn set_reset_delay(&mut self, reset_delay: Duration) {
+        self.reset_delay = reset_delay;
+    }
+
+    /// Get the time required by the strip to latch the data after transmission
+    pub fn reset_delay(&self) -> Duration {
+        self.reset_delay
+    }
+
+    /// Render the strip as a row of ANSI true-color blocks, two characters per LED, for previewing frames in a terminal.
+    /// White channel is approximated by adding it to RGB.
+    pub fn dump_ansi(&self) -> String {
+        let mut output: String = self
+            .leds
+            .iter()
+            .map(|led| {
+                let color = Led::from_rgb(led.r, led.g, led.b) + Led::from_rgb(led.w, led.w, led.w);
+                format!("\x1b[48;2;{};{};{}m  ", color.r, color.g, color.b)
+            })
+            .collect();
+        output.push_str("\x1b[0m");
+        output
+    }
+
+    /// Get raw bytes that `update` would send to the strip via SPI, with gamma, white balance, brightness,
+    /// color order and pixel format applied. Temporal dithering is not applied, as it depends on previous frames.
+    pub fn encode(&self) -> Vec<u8> {
+        self.raw_led_data().collect()
+    }
+
+    /// Same as `encode`, but writes the bytes into provided buffer (replacing its contents), to reuse its memory
+    pub fn encode_into(&self, buffer: &mut Vec<u8>) {
+        buffer.clear();
+        buffer.extend(self.raw_led_data());
+    }
+
+    /// Estimate the current (in milliamps) drawn by the strip when displaying current frame.
+    /// The estimation uses colors as they would be sent to the strip (with gamma and brightness applied),
+    /// assuming that each element draws current proportional to its value.
+    pub fn estimated_current_ma(&self) -> u32 {
+        let channels_current: u64 = self
+            .leds
+            .iter()
+            .map(|led| {
+                let led = self.output_color(led);
+                led.r as u64 * RED_CURRENT_MA as u64
+                    + led.g as u64 * GREEN_CURRENT_MA as u64
+                    + led.b as u64 * BLUE_CURRENT_MA as u64
+                    + led.w as u64 * WHITE_CURRENT_MA as u64
+            })
+            .sum();
+        let quiescent_current = self.leds.len() as u64 * QUIESCENT_CURRENT_MA as u64;
+
+        (quiescent_current + channels_current / u8::MAX as u64) as u32
+    }
+
+    /// Scale the colors of all LEDs down, so the estimated current (see `estimated_current_ma`) fits in `max_ma` budget.
+    /// Colors stored in `leds` are modified. If the strip already fits in the budget, nothing is changed.
+    /// Since gamma correction dims scaled colors even more, the resulting current may end up noticeably below the budget.
+    pub fn limit_power(&mut self, max_ma: u32) {
+        let current = self.estimated_current_ma();
+        if current <= max_ma {
+            return;
+        }
+
+        let quiescent_current = self.leds.len() as u32 * QUIESCENT_CURRENT_MA;
+        let scale =
+            max_ma.saturating_sub(quiescent_current) as f32 / (current - quiescent_current) as f32;
+
+        // Round the channels down, so the scaled colors never exceed the budget
+        let scale_channel = |value: u8| (value as f32 * scale) as u8;
+        self.leds.iter_mut().for_each(|led| {
+            *led = Led::from_rgbw(
+                scale_channel(led.r),
+                scale_channel(led.g),
+                scale_channel(led.b),
+                scale_channel(led.w),
+            )
+        });
+    }
+
+    /// Get statistics of time spent in `update` over the last 64 updates
+    #[cfg(feature = "metrics")]
+    pub fn timing_stats(&self) -> TimingStats {
+        let samples = self.update_times.len();
+        if samples == 0 {
+            return TimingStats::default();
+        }
+
+        TimingStats {
+            min: self.update_times.iter().copied().min().unwrap_or_default(),
+            max: self.update_times.iter().copied().max().unwrap_or_default(),
+            average: self.update_times.iter().sum::<Duration>() / samples as u32,
+            samples,
+        }
+    }
+
+    /// Check if the strip is in standby, see `standby`. The flag is cleared by the next `update`.
+    pub fn is_standby(&self) -> bool {
+        self.standby
+    }
+
+    /// Check if the strip has latched the last frame sent by `update_nonblocking` at given moment,
+    /// and is ready for next one.
+    pub fn is_ready(&self, now: Instant) -> bool {
+        now >= self.ready_at
+    }
+
+    // Encode raw LED data into the transmit buffer, reusing its memory between frames
+    fn encode_buffer(&mut self) {
+        let mut buffer = mem::take(&mut self.buffer);
+        buffer.clear();
+
+        if self.dithering {
+            let mut dither_error = mem::take(&mut self.dither_error);
+            dither_error.resize(self.leds.len(), [0.0; 4]);
+
+            for (led, error) in self.leds.iter().zip(dither_error.iter_mut()) {
+                let color = self.dithered_color(led, error);
+                buffer.extend(
+                    color
+                        .raw_led_bytes(self.color_order)
+                        .into_iter()
+                        .take(self.pixel_format.raw_bytes_per_led()),
+                );
+            }
+
+            self.dither_error = dither_error;
+        } else {
+            buffer.extend(self.raw_led_data());
+        }
+
+        self.buffer = buffer;
+    }
+
+    // Get raw LED bytes, ready to send via SPI. This function is called internally by the library and usually you don't need to worry about it.
+    fn raw_led_data(&self) -> impl Iterator<Item = u8> + '_ {
+        self.leds.iter().flat_map(|led| {
+            self.output_color(led)
+                .raw_led_bytes(self.color_order)
+                .into_iter()
+                .take(self.pixel_format.raw_bytes_per_led())
+        })
+    }
+
+    // Get the LED color with gamma correction, white balance and brightness applied, as it should be sent to the strip.
+    // White is turned off for strips without white channel.
+    fn output_color(&self, led: &Led) -> Led {
+        let brightness = Led::from_rgbw(
+            self.brightness,
+            self.brightness,
+            self.brightness,
+            self.brightness,
+        ) * Led::from_rgbw(
+            self.rgb_brightness,
+            self.rgb_brightness,
+            self.rgb_brightness,
+            self.white_brightness,
+        );
+
+        self.corrected_color(led) * brightness
+    }
+
+    // Same as `output_color`, but brightness is applied with fractional precision.
+    // Rounding error is accumulated in `error` and carried to the next frame.
+    fn dithered_color(&self, led: &Led, error: &mut [f32; 4]) -> Led {
+        let max = u8::MAX as f32;
+        let brightness = self.brightness as f32 / max;
+        let rgb_scale = brightness * self.rgb_brightness as f32 / max;
+        let white_scale = brightness * self.white_brightness as f32 / max;
+
+        let corrected = self.corrected_color(led);
+        let dither = |value: u8, scale: f32, error: &mut f32| {
+            let target = value as f32 * scale + *error;
+            let output = target.round().clamp(0.0, max);
+            *error = target - output;
+            output as u8
+        };
+
+        Led {
+            r: dither(corrected.r, rgb_scale, &mut error[0]),
+            g: dither(corrected.g, rgb_scale, &mut error[1]),
+            b: dither(corrected.b, rgb_scale, &mut error[2]),
+            w: dither(corrected.w, white_scale, &mut error[3]),
+        }
+    }
+
+    // Apply gamma correction and white balance, turn off white for strips without white channel
+    fn corrected_color(&self, led: &Led) -> Led {
+        let [r_gain, g_gain, b_gain] = self.white_balance;
+        let [r_table, g_table, b_table, w_table] = &self.gamma_tables;
+        let balance =
+            |value: u8, gain: f32| (value as f32 * gain).round().min(u8::MAX as f32) as u8;
+
+        Led {
+            r: balance(r_table[led.r as usize], r_gain),
+            g: balance(g_table[led.g as usize], g_gain),
+            b: balance(b_table[led.b as usize], b_gain),
+            w: match self.pixel_format {
+                PixelFormat::Rgbw => w_table[led.w as usize],
+                PixelFormat::Rgb => 0,
+            },
+        }
+    }
+}
+
+// Map heat value to black-red-yellow-white fire color
+fn heat_color(heat: u8) -> Led {
+    // Scale the heat down to 0..=191, so it's divided into three equal 64-step ranges
+    let heat = (heat as u16 * 192 / 256) as u8;
+    let ramp = (heat & 0x3F) << 2;
+
+    match heat >> 6 {
+        0 => Led::from_rgb(ramp, 0, 0),
+        1 => Led::from_rgb(u8::MAX, ramp, 0),
+        _ => Led::from_rgb(u8::MAX, u8::MAX, ramp),
+    }
+}
+
+// Read kernel SPI buffer size (maximal size of a single SPI transfer) from `spidev.bufsiz` parameter file.
+// Returns `None` if the file doesn't exist (for example when spidev is not loaded) or is malformed.
+#[cfg(feature = "rppal")]
+fn read_spi_buffer_size(path: impl AsRef<Path>) -> Option<usize> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+// Precompute gamma correction lookup table for every possible channel value
+pub(crate) fn gamma_table(gamma: f32) -> [u8; 256] {
+    let mut table = [0; 256];
+    table.iter_mut().enumerate().for_each(|(value, entry)| {
+        *entry = ((value as f32 / u8::MAX as f32).powf(gamma) * u8::MAX as f32).round() as u8;
+    });
+    table
+}
+
+impl<SPI: SpiBus> Strip<SPI>
+where
+    SPI::Error: 'static,
+{
+    /// Call this to send the data from `leds` to the strip
+    /// This function will block the thread for the reset delay (80us by default, see `set_reset_delay`) after sending the data,
+    /// which is caused by strip comms protocol requirements.
+    ///
+    /// Frames larger than maximal frame size (see `set_max_frame_size`) are rejected without sending anything.
+    /// If you're getting an error, telling you that the message is too long - increase the SPI transfer size in `/boot/cmdline.txt`.
+    /// To do so, add `spidev.bufsiz=65535` to the first line of this file. I added it right before `rootwait`, but placement shouldn't matter.
+    pub fn update(&mut self) -> Result<(), StripError<SPI::Error>> {
+        let ready_at = self.update_nonblocking()?;
+        thread::sleep(ready_at.saturating_duration_since(Instant::now()));
+
+        Ok(())
+    }
+
+    /// Set the color of LED at `index` and send the data to the strip.
+    /// Returns an error without sending anything if the index is out of range.
+    /// Since the strip protocol requires sending the data from the first LED, the whole strip is sent.
+    pub fn set_and_update(&mut self, index: usize, led: Led) -> Result<(), StripError<SPI::Error>> {
+        self.set(index, led)?;
+        self.update()
+    }
+
+    /// Same as `update`, but returns the amount of bytes accepted by the SPI bus, summed over all transfers.
+    /// Partial writes are reported by the bus as errors, in which case the error is returned instead.
+    pub fn update_verbose(&mut self) -> Result<usize, StripError<SPI::Error>> {
+        let written = self.transmit()?;
+        thread::sleep(self.ready_at.saturating_duration_since(Instant::now()));
+
+        Ok(written)
+    }
+
+    /// Send the data from `leds` to the strip without waiting for the reset period to elapse.
+    /// Returns the moment after which the strip latches the data and is ready for next frame.
+    ///
+    /// Sending another frame before that moment (see `is_ready`) will likely corrupt the displayed frame,
+    /// since the strip will treat it as a continuation of the previous one.
+    pub fn update_nonblocking(&mut self) -> Result<Instant, StripError<SPI::Error>> {
+        self.transmit()?;
+        Ok(self.ready_at)
+    }
+
+    // Encode and send the frame, returning the amount of bytes written to the SPI bus
+    fn transmit(&mut self) -> Result<usize, StripError<SPI::Error>> {
+        #[cfg(feature = "metrics")]
+        let started_at = Instant::now();
+        self.encode_buffer();
+        let message_length = self.buffer.len();
+        if let Some(max) = self.max_frame_size.filter(|&max| message_length > max) {
+            return Err(StripError::FrameTooLarge {
+                needed: message_length,
+                max,
+            });
+        }
+
+        // Split the frame at LED boundaries, so every chunk fits in a single SPI transfer
+        let bytes_per_led = self.pixel_format.raw_bytes_per_led();
+        let chunk_size = match self.max_transfer_size {
+            Some(max) => (max / bytes_per_led).max(1) * bytes_per_led,
+            None => message_length,
+        };
+
+        let mut written = 0;
+        for chunk in self.buffer.chunks(chunk_size.max(1)) {
+            self.spi
+                .write(chunk)
+                .map_err(|error| StripError::from_spi_error(error, message_length))?;
+            written += chunk.len();
+        }
+        self.spi
+            .flush()
+            .map_err(|error| StripError::from_spi_error(error, message_length))?;
+        self.ready_at = Instant::now() + self.reset_delay;
+        self.standby = false;
+
+        #[cfg(feature = "metrics")]
+        {
+            if self.update_times.len() == TIMING_WINDOW {
+                self.update_times.pop_front();
+            }
+            self.update_times.push_back(started_at.elapsed());
+        }
+
+        Ok(written)
+    }
+
+    /// Turn off all the LEDs, send the off frame to the strip and mark it as idle.
+    /// Standby lasts until the next frame is sent with `update` or `update_nonblocking` - drawing alone doesn't end it,
+    /// since `leds` can be modified directly.
+    pub fn standby(&mut self) -> Result<(), StripError<SPI::Error>> {
+        self.clear();
+        self.update()?;
+        self.standby = true;
+
+        Ok(())
+    }
+
+    /// Send the data to the strip, keeping a steady frame rate of `fps` frames per second.
+    /// Sleeps until `1 / fps` seconds have passed since `last_frame`, calls `update` and sets `last_frame`
+    /// to the moment the frame was sent. If the frame is already late, it's sent immediately without sleeping.
+    pub fn update_at_fps(
+        &mut self,
+        fps: u32,
+        last_frame: &mut Instant,
+    ) -> Result<(), StripError<SPI::Error>> {
+        let next_frame = *last_frame + Duration::from_secs(1) / fps.max(1);
+        let now = Instant::now();
+        if next_frame > now {
+            thread::sleep(next_frame - now);
+        }
+
+        *last_frame = next_frame.max(now);
+        self.update()
+    }
+
+    /// Smoothly transition from current colors to `target` colors over `steps` frames, sending every frame
+    /// to the strip `interval` apart. Every LED is interpolated linearly. Target length must match the strip length.
+    /// Blocks the thread until the transition is finished.
+    pub fn crossfade(
+        &mut self,
+        target: &[Led],
+        steps: usize,
+        interval: Duration,
+    ) -> Result<(), StripError<SPI::Error>> {
+        if target.len() != self.leds.len() {
+            return Err(StripError::LengthMismatch(LengthError {
+                expected: self.leds.len(),
+                actual: target.len(),
+            }));
+        }
+
+        let start = self.snapshot();
+        let steps = steps.max(1);
+        for step in 1..=steps {
+            let t = step as f32 / steps as f32;
+            self.leds
+                .iter_mut()
+                .zip(start.iter().zip(target))
+                .for_each(|(led, (&from, &to))| *led = from.lerp(to, t));
+            self.update()?;
+
+            if step < steps {
+                thread::sleep(interval);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Run `effect` for `frames` frames, stepping it and sending every frame to the strip `interval` apart.
+    /// Blocks the thread until all frames are sent.
+    pub fn run_effect<E: Effect<SPI>>(
+        &mut self,
+        effect: &mut E,
+        frames: usize,
+        interval: Duration,
+    ) -> Result<(), StripError<SPI::Error>> {
+        let mut last_frame = Instant::now();
+        let mut dt = interval;
+
+        for _ in 0..frames {
+            effect.step(self, dt);
+            self.update()?;
+
+            let next_frame = last_frame + interval;
+            thread::sleep(next_frame.saturating_duration_since(Instant::now()));
+            let now = Instant::now();
+            dt = now - last_frame;
+            last_frame = now;
+        }
+
+        Ok(())
+    }
+}
+
+impl<SPI> Index<usize> for Strip<SPI> {
+    type Output = Led;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.leds[index]
+    }
+}
+
+impl<SPI> IndexMut<usize> for Strip<SPI> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.leds[index]
+    }
+}
+
+impl<'a, SPI> IntoIterator for &'a Strip<SPI> {
+    type Item = &'a Led;
+    type IntoIter = slice::Iter<'a, Led>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, SPI> IntoIterator for &'a mut Strip<SPI> {
+    type Item = &'a mut Led;
+    type IntoIter = slice::IterMut<'a, Led>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<SPI> ShrAssign<usize> for Strip<SPI> {
+    fn shr_assign(&mut self, rhs: usize) {
+        self.shift_right(rhs);
+    }
+}
+
+impl<SPI> ShlAssign<usize> for Strip<SPI> {
+    fn shl_assign(&mut self, rhs: usize) {
+        self.shift_left(rhs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockSpi, FRAME_HISTORY_LEN};
+    use palette::Hsv;
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::convert::Infallible;
+
+    fn make_strip() -> Strip<MockSpi> {
+        Strip::new_mock(144)
+    }
+
+    #[test]
+    fn test_len() {
+        assert_eq!(make_strip().len(), 144);
+        assert!(!make_strip().is_empty());
+        assert!(Strip::new_mock(0).is_empty());
+    }
+
+    #[test]
+    fn test_iteration() {
+        let mut strip = Strip::new_mock(5);
+
+        strip
+            .iter_mut()
+            .enumerate()
+            .for_each(|(index, led)| led.w = index as u8);
+
+        assert!(strip
+            .iter()
+            .enumerate()
+            .all(|(index, led)| led.w == index as u8));
+
+        for led in &mut strip {
+            led.r = 10;
+        }
+        for led in &strip {
+            assert_eq!(led.r, 10);
+        }
+    }
+
+    #[test]
+    fn test_resize() {
+        let led = Led::from_rgbw(1, 2, 3, 4);
+        let mut strip = Strip::new_mock(3);
+        strip.fill(led);
+
+        strip.resize(5);
+        assert_eq!(strip.leds, [led, led, led, Led::new(), Led::new()]);
+
+        strip.resize(2);
+        assert_eq!(strip.leds, [led, led]);
+
+        strip.update().unwrap();
+        assert_eq!(strip.last_frame().len(), 2 * 32);
+    }
+
+    #[test]
+    fn test_setting_color() {
+        let led: Led = [100, 0, 0].into();
+        let mut strip = make_strip();
+        strip.set_gamma(1.0);
+
+        strip.fill(led);
+        strip.update().unwrap();
+
+        strip.leds.iter().for_each(|strip_led| {
+            assert_eq!(*strip_led, led);
+        });
+        assert_eq!(strip.last_frame(), led.to_raw_led_bytes().repeat(144));
+    }
+
+    #[test]
+    fn test_clearing() {
+        let led: Led = [100, 0, 0].into();
+        let mut strip = make_strip();
+
+        strip.fill(led);
+        strip.clear();
+
+        strip.leds.iter().for_each(|strip_led| {
+            assert_eq!(*strip_led, Led::new());
+        })
+    }
+
+    #[test]
+    fn test_double_buffering() {
+        let led: Led = [100, 0, 0].into();
+        let mut strip = make_strip();
+
+        strip.back().fill(led);
+        assert!(strip.leds.iter().all(|strip_led| *strip_led == Led::new()));
+
+        strip.swap_buffers();
+        assert!(strip.leds.iter().all(|strip_led| *strip_led == led));
+        assert!(strip.back().iter().all(|back_led| *back_led == Led::new()));
+    }
+
+    #[test]
+    fn test_breathing() {
+        let base = Led::from_rgbw(200, 100, 50, 20);
+        let mut strip = make_strip();
+
+        strip.apply_breathing(base, -std::f32::consts::FRAC_PI_2);
+        assert!(strip.leds.iter().all(|led| *led == Led::new()));
+
+        strip.apply_breathing(base, std::f32::consts::FRAC_PI_2);
+        assert!(strip.leds.iter().all(|led| *led == base));
+    }
+
+    #[test]
+    fn test_theater_chase() {
+        let led: Led = [100, 0, 0].into();
+        let mut strip = Strip::new_mock(9);
+
+        strip.theater_chase(led, 3, 0);
+
+        strip
+            .leds
+            .iter()
+            .enumerate()
+            .for_each(|(index, strip_led)| {
+                if [0, 3, 6].contains(&index) {
+                    assert_eq!(*strip_led, led);
+                } else {
+                    assert_eq!(*strip_led, Led::new());
+                }
+            });
+
+        strip.theater_chase(led, 3, 1);
+        assert_eq!(strip.leds[0], Led::new());
+        assert_eq!(strip.leds[2], led);
+    }
+
+    #[test]
+    fn test_comet() {
+        let color = Led::from_rgbw(200, 100, 50, 0);
+        let mut strip = Strip::new_mock(10);
+
+        strip.comet(6, color, 3, 0.5);
+
+        assert_eq!(strip.leds[6], color);
+        assert!(strip.leds[3..=6]
+            .windows(2)
+            .all(|pair| pair[0].r < pair[1].r));
+        assert!(strip.leds[..3].iter().all(|led| *led == Led::new()));
+        assert!(strip.leds[7..].iter().all(|led| *led == Led::new()));
+    }
+
+    #[test]
+    fn test_fill_gradient() {
+        let start = Led::from_rgbw(200, 10, 0, 0);
+        let end = Led::from_rgbw(0, 100, 250, 100);
+        let mut strip = Strip::new_mock(11);
+
+        strip.fill_gradient(start, end);
+
+        assert_eq!(strip.leds[0], start);
+        assert_eq!(strip.leds[10], end);
+
+        let middle = strip.leds[5];
+        assert!(middle.r < start.r && middle.r > end.r);
+        assert!(middle.g > start.g && middle.g < end.g);
+        assert!(middle.b > start.b && middle.b < end.b);
+        assert_eq!(middle.w, 50);
+    }
+
+    #[test]
+    fn test_twinkle() {
+        let base = Led::from_rgb(0, 0, 10);
+        let color = Led::from_rgbw(0, 0, 0, 255);
+        let lit_leds = |seed: u64| {
+            let mut strip = Strip::new_mock(20);
+            strip.fill(base);
+            strip.twinkle(color, 0.25, &mut StdRng::seed_from_u64(seed));
+
+            assert!(strip.iter().all(|led| *led == base || *led == color));
+            strip
+                .iter()
+                .enumerate()
+                .filter(|(_, led)| **led == color)
+                .map(|(index, _)| index)
+                .collect::<Vec<usize>>()
+        };
+
+        let lit = lit_leds(42);
+        assert_eq!(lit.len(), 5);
+        assert_eq!(lit_leds(42), lit);
+    }
+
+    #[test]
+    fn test_color_wipe() {
+        let color = Led::from_rgb(0, 100, 0);
+        let mut strip = Strip::new_mock(5);
+
+        strip.color_wipe_step(color, 2);
+        assert_eq!(strip.leds[..=2], [color; 3]);
+        assert_eq!(strip.leds[3..], [Led::new(); 2]);
+
+        strip.color_wipe_step(color, 100);
+        assert_eq!(strip.leds, [color; 5]);
+    }
+
+    #[test]
+    fn test_fire() {
+        let mut strip = Strip::new_mock(30);
+        let mut rng = StdRng::seed_from_u64(2137);
+
+        (0..100).for_each(|_| {
+            strip.fire_step(55, 120, &mut rng);
+
+            // Fire palette goes from black through red and yellow to white
+            assert!(strip
+                .iter()
+                .all(|led| led.r >= led.g && led.g >= led.b && led.w == 0));
+        });
+
+        assert!(strip.iter().any(|led| led.r > 0));
+    }
+
+    #[test]
+    fn test_copy_from_slice() {
+        let frame = [
+            Led::from_rgb(1, 0, 0),
+            Led::from_rgb(2, 0, 0),
+            Led::from_rgb(3, 0, 0),
+        ];
+        let mut strip = Strip::new_mock(3);
+
+        assert_eq!(strip.copy_from_slice(&frame), Ok(()));
+        assert_eq!(strip.leds, frame);
+
+        strip.clear();
+        assert_eq!(
+            strip.copy_from_slice(&frame[..2]),
+            Err(LengthError {
+                expected: 3,
+                actual: 2
+            })
+        );
+        assert_eq!(strip.leds, [Led::new(); 3]);
+
+        assert_eq!(strip.copy_from_slice_clamped(&frame[..2]), 2);
+        assert_eq!(strip.leds, [frame[0], frame[1], Led::new()]);
+    }
+
+    #[test]
+    fn test_blink() {
+        let color = Led::from_rgbw(10, 20, 30, 40);
+        let mut strip = Strip::new_mock(4);
+
+        strip.blink(color, true);
+        assert!(strip.iter().all(|led| *led == color));
+
+        strip.blink(color, false);
+        strip.update().unwrap();
+        assert_eq!(strip.last_frame(), Led::new().to_raw_led_bytes().repeat(4));
+
+        strip.blink_range(1..3, color, true);
+        assert_eq!(strip.leds, [Led::new(), color, color, Led::new()]);
+    }
+
+    #[test]
+    fn test_segment() {
+        let mut strip = Strip::new_mock(6);
+
+        strip.segment(0..2).fill(Led::from_rgb(255, 0, 0));
+        strip.segment(2..6).fill(Led::from_rgb(0, 0, 255));
+        strip.segment(4..10).clear();
+
+        assert_eq!(strip.leds[..2], [Led::from_rgb(255, 0, 0); 2]);
+        assert_eq!(strip.leds[2..4], [Led::from_rgb(0, 0, 255); 2]);
+        assert_eq!(strip.leds[4..], [Led::new(); 2]);
+        assert!(strip.segment(8..10).is_empty());
+    }
+
+    #[test]
+    fn test_set_gradient_gamma() {
+        let gradient = || {
+            Gradient::new(vec![
+                LinSrgb::new(0.0, 0.0, 0.0),
+                LinSrgb::new(1.0, 1.0, 1.0),
+            ])
+        };
+        let mut strip = Strip::new_mock(10);
+        let mut corrected = Strip::new_mock(10);
+
+        strip.set_gradient(gradient());
+        corrected.set_gradient_gamma(gradient(), 2.8);
+
+        assert!(corrected.leds[1].r < strip.leds[1].r);
+        assert_eq!(corrected.leds[0], Led::new());
+        assert!(corrected.leds[9].r <= strip.leds[9].r);
+
+        corrected.set_gradient_gamma(gradient(), 1.0);
+        assert_eq!(corrected.leds, strip.leds);
+    }
+
+    #[test]
+    fn test_write_frame() {
+        let mut frame = Frame::new(3);
+        frame.fill_gradient(Led::new(), Led::from_rgb(255, 255, 255));
+        let mut strip = Strip::new_mock(3);
+
+        strip.write_frame(&frame).unwrap();
+        assert_eq!(strip.leds, frame.leds);
+
+        assert_eq!(
+            strip.write_frame(&Frame::new(2)),
+            Err(LengthError {
+                expected: 3,
+                actual: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_is_all_off() {
+        let mut strip = Strip::new_mock(5);
+        assert!(strip.is_all_off());
+        assert_eq!(strip.count_lit(), 0);
+
+        strip.fill_range(1..3, Led::from_rgbw(0, 0, 0, 1));
+        assert!(!strip.is_all_off());
+        assert_eq!(strip.count_lit(), 2);
+
+        strip.clear();
+        assert!(strip.is_all_off());
+    }
+
+    #[test]
+    fn test_gamma_per_channel() {
+        let mut strip = Strip::new_mock(1);
+        strip.fill(Led::from_rgbw(128, 128, 128, 128));
+
+        strip.set_gamma_per_channel(1.0, 1.5, 2.2, 2.8);
+        assert_eq!(strip.gamma_per_channel(), [1.0, 1.5, 2.2, 2.8]);
+        strip.update().unwrap();
+
+        let expected = Led::from_rgbw(
+            128,
+            gamma_table(1.5)[128],
+            gamma_table(2.2)[128],
+            gamma_table(2.8)[128],
+        );
+        assert_eq!(strip.last_frame(), expected.to_raw_led_bytes());
+        assert!(expected.r > expected.g && expected.g > expected.b && expected.b > expected.w);
+
+        strip.set_gamma(1.0);
+        assert_eq!(strip.gamma_per_channel(), [1.0; 4]);
+    }
+
+    #[test]
+    fn test_composite() {
+        let base = Led::from_rgbw(200, 0, 100, 0);
+        let overlay = [Led::from_rgbw(0, 200, 100, 50); 3];
+        let mut strip = Strip::new_mock(3);
+        strip.fill(base);
+
+        strip.composite(&overlay, 0.0).unwrap();
+        assert!(strip.iter().all(|led| *led == base));
+
+        strip.composite(&overlay, 0.5).unwrap();
+        assert!(strip
+            .iter()
+            .all(|led| *led == Led::from_rgbw(100, 100, 100, 25)));
+
+        strip.composite(&overlay, 1.0).unwrap();
+        assert_eq!(strip.leds, overlay);
+
+        assert!(strip.composite(&overlay[..2], 1.0).is_err());
+    }
+
+    #[test]
+    fn test_encode() {
+        let mut strip = Strip::new_mock(1);
+        strip.fill(Led::from_rgbw(0x80, 0x01, 0xff, 0x00));
+        strip.set_gamma(1.0);
+        strip.set_color_order(ColorOrder::Rgbw);
+
+        let high = 0b11110000;
+        let low = 0b11000000;
+        let mut expected = Vec::new();
+        expected.extend([high, low, low, low, low, low, low, low]);
+        expected.extend([low, low, low, low, low, low, low, high]);
+        expected.extend([high; 8]);
+        expected.extend([low; 8]);
+        assert_eq!(strip.encode(), expected);
+
+        strip.set_brightness(0);
+        let mut buffer = vec![1, 2, 3];
+        strip.encode_into(&mut buffer);
+        assert_eq!(buffer, [low; 32]);
+
+        strip.set_brightness(u8::MAX);
+        strip.update().unwrap();
+        assert_eq!(strip.last_frame(), strip.encode());
+    }
+
+    #[test]
+    fn test_reset_delay() {
+        let mut strip = Strip::new_mock(1);
+        assert_eq!(strip.reset_delay(), Duration::from_micros(80));
+
+        let delay = Duration::from_millis(20);
+        strip.set_reset_delay(delay);
+        assert_eq!(strip.reset_delay(), delay);
+
+        let before = Instant::now();
+        let ready_at = strip.update_nonblocking().unwrap();
+        assert!(ready_at >= before + delay);
+        assert!(!strip.is_ready(before + delay / 2));
+
+        strip.update().unwrap();
+        assert!(Instant::now() >= before + delay);
+    }
+
+    #[test]
+    fn test_for_each_indexed() {
+        let mut strip = make_strip();
+
+        strip.for_each_indexed(|index, led| led.w = index as u8);
+
+        assert!(strip
+            .iter()
+            .enumerate()
+            .all(|(index, led)| *led == Led::from_rgbw(0, 0, 0, index as u8)));
+    }
+
+    #[test]
+    fn test_fade_to_black_by() {
+        let mut strip = Strip::new_mock(2);
+        strip.leds[0] = Led::from_rgbw(100, 50, 20, 10);
+        strip.leds[1] = Led::from_rgbw(255, 255, 255, 255);
+
+        strip.fade_to_black_by(10);
+        assert_eq!(strip.leds[0], Led::from_rgbw(90, 40, 10, 0));
+        assert_eq!(strip.leds[1], Led::from_rgbw(245, 245, 245, 245));
+
+        for _ in 0..25 {
+            strip.fade_to_black_by(10);
+        }
+        assert!(strip.is_all_off());
+    }
+
+    #[test]
+    fn test_fill_gradient_stops() {
+        let mut strip = Strip::new_mock(3);
+
+        strip.fill_gradient_stops(&[
+            (0.0, Led::from_rgb(255, 0, 0)),
+            (0.5, Led::from_rgbw(0, 0, 0, 255)),
+            (1.0, Led::from_rgb(0, 0, 255)),
+        ]);
+        assert_eq!(strip.leds[1], Led::from_rgbw(0, 0, 0, 255));
+        assert_eq!(strip.leds[2], Led::from_rgb(0, 0, 255));
+    }
+
+    #[test]
+    fn test_run_effect() {
+        struct Counter(usize);
+
+        impl Effect<MockSpi> for Counter {
+            fn step(&mut self, strip: &mut Strip<MockSpi>, dt: Duration) {
+                assert!(dt >= Duration::from_millis(1));
+                strip.fill(Led::from_rgbw(0, 0, 0, self.0 as u8));
+                self.0 += 1;
+            }
+        }
+
+        let mut strip = Strip::new_mock(2);
+        strip.set_gamma(1.0);
+        let mut counter = Counter(1);
+        let start = Instant::now();
+
+        strip
+            .run_effect(&mut counter, 3, Duration::from_millis(1))
+            .unwrap();
+        assert_eq!(counter.0, 4);
+        assert!(start.elapsed() >= Duration::from_millis(3));
+        assert_eq!(
+            strip.last_frame(),
+            Led::from_rgbw(0, 0, 0, 3).to_raw_led_bytes().repeat(2)
+        );
+
+        let mut rainbow = crate::effect::Rainbow::new(360.0, 90.0);
+        strip
+            .run_effect(&mut rainbow, 2, Duration::from_millis(1))
+            .unwrap();
+        assert_ne!(strip.leds[0], strip.leds[1]);
+    }
+
+    #[test]
+    fn test_set_value() {
+        let led = Led::from_rgbw(200, 100, 0, 50);
+        let mut strip = Strip::new_mock(1);
+        strip.fill(led);
+
+        strip.set_value(0.5);
+
+        let original = Hsv::from(led);
+        let dimmed = Hsv::from(strip.leds[0]);
+        assert!(
+            (dimmed.hue.to_positive_degrees() - original.hue.to_positive_degrees()).abs() < 1.0
+        );
+        assert!((dimmed.saturation - original.saturation).abs() < 0.01);
+        assert!((dimmed.value - original.value * 0.5).abs() < 0.01);
+        assert_eq!(strip.leds[0].w, 50);
+    }
+
+    #[test]
+    fn test_snapshot_restore() {
+        let led = Led::from_rgbw(1, 2, 3, 4);
+        let mut strip = Strip::new_mock(4);
+        strip.fill(led);
+
+        let snapshot = strip.snapshot();
+        strip.clear();
+        assert!(strip.is_all_off());
+
+        strip.restore(&snapshot).unwrap();
+        assert!(strip.iter().all(|strip_led| *strip_led == led));
+        assert!(strip.restore(&snapshot[..3]).is_err());
+    }
+
+    #[test]
+    fn test_vu_meter() {
+        let low = Led::from_rgb(0, 255, 0);
+        let high = Led::from_rgb(255, 0, 0);
+        let mut strip = Strip::new_mock(10);
+
+        strip.vu_meter(0.5, low, high);
+        assert_eq!(strip.count_lit(), 5);
+        assert!(strip.leds[5..].iter().all(Led::is_off));
+        assert_eq!(strip.leds[0], low);
+        assert_eq!(strip.leds[3], low.lerp(high, 3.0 / 9.0));
+        assert!(strip.leds[4].r > strip.leds[3].r);
+
+        strip.vu_meter(0.25, low, high);
+        assert_eq!(strip.count_lit(), 3);
+        assert_eq!(strip.leds[2], low.lerp(high, 2.0 / 9.0) * 0.5);
+
+        strip.vu_meter(1.0, low, high);
+        assert_eq!(strip.leds[9], high);
+    }
+
+    #[test]
+    fn test_crossfade() {
+        let start = Led::from_rgbw(0, 200, 0, 100);
+        let target = [Led::from_rgbw(200, 0, 0, 0); 2];
+        let mut strip = Strip::new_mock(2);
+        strip.set_gamma(1.0);
+        strip.fill(start);
+
+        strip
+            .crossfade(&target, 4, Duration::from_millis(1))
+            .unwrap();
+        assert_eq!(strip.leds, target);
+        assert_eq!(strip.frames().len(), 4);
+        assert_eq!(strip.last_frame(), target[0].to_raw_led_bytes().repeat(2));
+
+        let halfway = Led::from_rgbw(100, 100, 0, 50).to_raw_led_bytes().repeat(2);
+        assert_eq!(strip.frames()[1], halfway);
+
+        assert!(matches!(
+            strip.crossfade(&target[..1], 4, Duration::ZERO),
+            Err(StripError::LengthMismatch(LengthError {
+                expected: 2,
+                actual: 1
+            }))
+        ));
+    }
+
+    // SPI bus accepting only `capacity` bytes in total, failing writes that don't fit
+    #[derive(Debug)]
+    struct ShortWriteSpi {
+        capacity: usize,
+    }
+
+    #[derive(Debug)]
+    struct ShortWriteError;
+
+    impl embedded_hal::spi::Error for ShortWriteError {
+        fn kind(&self) -> embedded_hal::spi::ErrorKind {
+            embedded_hal::spi::ErrorKind::Other
+        }
+    }
+
+    impl embedded_hal::spi::ErrorType for ShortWriteSpi {
+        type Error = ShortWriteError;
+    }
+
+    impl SpiBus for ShortWriteSpi {
+        fn read(&mut self, _words: &mut [u8]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+            self.capacity = self
+                .capacity
+                .checked_sub(words.len())
+                .ok_or(ShortWriteError)?;
+            Ok(())
+        }
+
+        fn transfer(&mut self, _read: &mut [u8], write: &[u8]) -> Result<(), Self::Error> {
+            self.write(write)
+        }
+
+        fn transfer_in_place(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
+            self.write(words)
+        }
+
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_update_verbose_short_write() {
+        let mut strip = Strip::from_spi_bus(ShortWriteSpi { capacity: 160 }, 3);
+        strip.set_max_transfer_size(Some(64));
+
+        assert_eq!(strip.update_verbose().unwrap(), 96);
+        assert!(matches!(
+            strip.update_verbose(),
+            Err(StripError::Spi(ShortWriteError))
+        ));
+    }
+
+    #[test]
+    fn test_update_verbose() {
+        let mut strip = Strip::new_mock(5);
+
+        assert_eq!(strip.update_verbose().unwrap(), strip.len() * 32);
+        assert_eq!(strip.last_frame().len(), strip.len() * 32);
+
+        strip.set_pixel_format(PixelFormat::Rgb);
+        assert_eq!(strip.update_verbose().unwrap(), strip.len() * 24);
+    }
+
+    #[test]
+    fn test_plasma_step() {
+        let mut strip = Strip::new_mock(16);
+
+        strip.plasma_step(0.0, 0.5);
+        let first = strip.snapshot();
+        // Fully saturated colors at full value always have one channel at max and one off
+        assert!(strip.iter().all(|led| {
+            let [r, g, b, w]: [u8; 4] = (*led).into();
+            w == 0 && r.max(g).max(b) == u8::MAX && r.min(g).min(b) == 0
+        }));
+
+        strip.plasma_step(1.0, 0.5);
+        assert_ne!(strip.leds, first);
+    }
+
+    #[test]
+    fn test_set_and_update() {
+        let led = Led::from_rgbw(1, 2, 3, 4);
+        let mut strip = Strip::new_mock(3);
+        strip.set_gamma(1.0);
+
+        let error = strip.set_and_update(3, led).unwrap_err();
+        assert!(matches!(
+            error,
+            StripError::OutOfBounds(IndexError { index: 3, len: 3 })
+        ));
+        assert!(strip.frames().is_empty());
+
+        strip.set_and_update(1, led).unwrap();
+        assert_eq!(strip.leds[1], led);
+        assert_eq!(strip.frames().len(), 1);
+        assert_eq!(strip.last_frame()[32..64], led.to_raw_led_bytes());
+
+        (0..FRAME_HISTORY_LEN * 2).for_each(|_| strip.update().unwrap());
+        assert_eq!(strip.frames().len(), FRAME_HISTORY_LEN);
+        assert_eq!(strip.last_frame()[32..64], led.to_raw_led_bytes());
+    }
+
+    #[test]
+    fn test_larson_scanner() {
+        let color = Led::from_rgb(200, 0, 0);
+        let mut strip = Strip::new_mock(10);
+
+        strip.larson_scanner(5, 4, color);
+        let brightness: Vec<u8> = strip.iter().map(|led| led.r).collect();
+        assert_eq!(brightness, [0, 0, 50, 100, 150, 200, 150, 100, 50, 0]);
+
+        strip.larson_scanner(0, 3, color);
+        assert_eq!(strip.leds[0], color);
+        assert!(strip.leds[0].r > strip.leds[1].r && strip.leds[1].r > strip.leds[2].r);
+        assert_eq!(strip.count_lit(), 3);
+
+        strip.larson_scanner(9, 1, color);
+        assert_eq!(strip.count_lit(), 1);
+        assert_eq!(strip.leds[9], color);
+    }
+
+    #[test]
+    fn test_dump_ansi() {
+        let mut strip = Strip::new_mock(3);
+        strip.leds[0] = Led::from_rgb(255, 0, 0);
+        strip.leds[1] = Led::from_rgbw(10, 20, 30, 100);
+
+        let dump = strip.dump_ansi();
+        assert_eq!(dump.matches("\x1b[48;2;").count(), 3);
+        assert!(dump.starts_with("\x1b[48;2;255;0;0m  \x1b[48;2;110;120;130m  \x1b[48;2;0;0;0m  "));
+        assert!(dump.ends_with("\x1b[0m"));
+        assert_eq!(Strip::new_mock(0).dump_ansi(), "\x1b[0m");
+    }
+
+    #[test]
+    fn test_standby() {
+        let mut strip = Strip::new_mock(4);
+        strip.fill(Led::from_rgbw(10, 20, 30, 40));
+        strip.update().unwrap();
+        assert!(!strip.is_standby());
+
+        strip.standby().unwrap();
+        assert!(strip.is_standby());
+        assert!(strip.is_all_off());
+        assert_eq!(strip.last_frame(), Strip::new_mock(4).encode());
+
+        strip.fill(Led::from_rgb(255, 0, 0));
+        assert!(strip.is_standby());
+        strip.update().unwrap();
+        assert!(!strip.is_standby());
+    }
+
+    #[test]
+    fn test_cycle_palette() {
+        let black = Led::new();
+        let white = Led::from_rgbw(0, 0, 0, 200);
+        let mut strip = Strip::new_mock(4);
+
+        strip.cycle_palette(&[black, white], 0.0);
+        assert_eq!(
+            strip.leds,
+            [
+                black,
+                Led::from_rgbw(0, 0, 0, 100),
+                white,
+                Led::from_rgbw(0, 0, 0, 100)
+            ]
+        );
+
+        strip.cycle_palette(&[black, white], 1.5);
+        assert_eq!(
+            strip.leds,
+            [
+                Led::from_rgbw(0, 0, 0, 100),
+                black,
+                Led::from_rgbw(0, 0, 0, 100),
+                white
+            ]
+        );
+
+        strip.cycle_palette(&[black, white], -2.0);
+        assert_eq!(strip.leds[0], black);
+        assert_eq!(strip.leds[2], white);
+
+        strip.cycle_palette(&[], 0.0);
+        assert!(strip.is_all_off());
+    }
+
+    #[test]
+    #[cfg(feature = "metrics")]
+    fn test_timing_stats() {
+        let mut strip = Strip::new_mock(16);
+        assert_eq!(strip.timing_stats(), TimingStats::default());
+
+        for _ in 0..5 {
+            strip.update().unwrap();
+        }
+        let stats = strip.timing_stats();
+        assert_eq!(stats.samples, 5);
+        assert!(stats.min <= stats.average && stats.average <= stats.max);
+        assert!(stats.max > Duration::ZERO);
+
+        for _ in 0..TIMING_WINDOW {
+            strip.update().unwrap();
+        }
+        assert_eq!(strip.timing_stats().samples, TIMING_WINDOW);
+    }
+
+    #[test]
+    fn test_set_from_iter() {
+        let base = Led::from_rgb(1, 2, 3);
+        let mut strip = Strip::new_mock(5);
+        strip.fill(base);
+
+        strip.set_from_iter((1..=3).map(|value| Led::from_rgbw(0, 0, 0, value)));
+        assert_eq!(
+            strip.leds,
+            [
+                Led::from_rgbw(0, 0, 0, 1),
+                Led::from_rgbw(0, 0, 0, 2),
+                Led::from_rgbw(0, 0, 0, 3),
+                base,
+                base
+            ]
+        );
+
+        strip.set_from_iter(std::iter::repeat(Led::new()));
+        assert!(strip.is_all_off());
+    }
+
+    #[test]
+    fn test_fill_range() {
+        let led: Led = [100, 0, 0].into();
+        let mut strip = Strip::new_mock(10);
+
+        strip.fill_range(2..5, led);
+
+        strip
+            .leds
+            .iter()
+            .enumerate()
+            .for_each(|(index, strip_led)| {
+                if (2..5).contains(&index) {
+                    assert_eq!(*strip_led, led);
+                } else {
+                    assert_eq!(*strip_led, Led::new());
+                }
+            });
+
+        strip.fill_range(8..=20, led);
+        assert_eq!(strip.leds[7], Led::new());
+        assert_eq!(strip.leds[8], led);
+        assert_eq!(strip.leds[9], led);
+
+        strip.fill_range(.., Led::new());
+        assert!(strip.leds.iter().all(|strip_led| *strip_led == Led::new()));
+    }
+
+    #[test]
+    fn test_fill_rainbow() {
+        let mut strip = Strip::new_mock(4);
+
+        strip.fill_rainbow(0.0, 120.0);
+
+        assert_eq!(strip.leds[0], Led::from_rgb(255, 0, 0));
+        assert_eq!(strip.leds[1], Led::from_rgb(0, 255, 0));
+        assert_eq!(strip.leds[2], Led::from_rgb(0, 0, 255));
+        assert_eq!(strip.leds[3], Led::from_rgb(255, 0, 0));
+    }
+
+    #[test]
+    fn test_animate_rainbow() {
+        let mut strip = Strip::new_mock(4);
+
+        strip.animate_rainbow(0.0, 360.0);
+        assert_eq!(strip.leds[0], Led::from_rgb(255, 0, 0));
+        let first = strip.leds[0];
+
+        strip.animate_rainbow(120.0, 360.0);
+        assert_ne!(strip.leds[0], first);
+        assert_eq!(strip.leds[0], Led::from_rgb(0, 255, 0));
+        assert_eq!(strip.leds[2], Led::from_rgb(255, 0, 255));
+    }
+
+    #[test]
+    fn test_mirror() {
+        let mut strip = Strip::new_mock(4);
+        strip.leds[0].r = 1;
+        strip.leds[1].r = 2;
+        strip.leds[2].r = 3;
+        strip.leds[3].r = 4;
+
+        strip.mirror();
+
+        assert_eq!(
+            strip.leds.iter().map(|led| led.r).collect::<Vec<u8>>(),
+            [1, 2, 2, 1]
+        );
+
+        let mut strip = Strip::new_mock(5);
+        strip.leds[0].r = 1;
+        strip.leds[1].r = 2;
+        strip.leds[2].r = 3;
+        strip.leds[3].r = 4;
+        strip.leds[4].r = 5;
+
+        strip.mirror();
+
+        assert_eq!(
+            strip.leds.iter().map(|led| led.r).collect::<Vec<u8>>(),
+            [1, 2, 3, 2, 1]
+        );
+    }
+
+    #[test]
+    fn test_reverse() {
+        let mut strip = Strip::new_mock(5);
+        strip.leds[0].r = 1;
+        strip.leds[1].r = 2;
+        strip.leds[2].r = 3;
+        strip.leds[3].r = 4;
+        strip.leds[4].r = 5;
+
+        strip.reverse();
+
+        assert_eq!(
+            strip.leds.iter().map(|led| led.r).collect::<Vec<u8>>(),
+            [5, 4, 3, 2, 1]
+        );
+    }
+
+    #[test]
+    fn test_scroll() {
+        let fill = Led::new();
+        let mut strip = Strip::new_mock(4);
+        (0..4).for_each(|index| strip.leds[index].r = index as u8 + 1);
+
+        strip.scroll_left(1, fill);
+        assert_eq!(
+            strip.iter().map(|led| led.r).collect::<Vec<_>>(),
+            [2, 3, 4, 0]
+        );
+        assert_eq!(strip.leds[3], fill);
+
+        strip.scroll_right(2, Led::from_rgb(9, 0, 0));
+        assert_eq!(
+            strip.iter().map(|led| led.r).collect::<Vec<_>>(),
+            [9, 9, 2, 3]
+        );
+
+        strip.scroll_left(10, fill);
+        assert!(strip.iter().all(|led| *led == fill));
+    }
+
+    #[test]
+    fn test_shift_right() {
+        let mut strip = Strip::new_mock(5);
+
+        strip.leds[0].r = 1;
+        strip.leds[1].r = 2;
+        strip.leds[2].r = 3;
+        strip.leds[3].r = 4;
+        strip.leds[4].r = 5;
+
+        strip >>= 1;
+
+        assert_eq!(strip.leds[0].r, 5);
+        assert_eq!(strip.leds[1].r, 1);
+        assert_eq!(strip.leds[2].r, 2);
+        assert_eq!(strip.leds[3].r, 3);
+        assert_eq!(strip.leds[4].r, 4);
+    }
+
+    #[test]
+    fn test_shift_right_with_indexing() {
+        let mut strip = Strip::new_mock(5);
+
+        strip[0].r = 1;
+        strip[1].r = 2;
+        strip[2].r = 3;
+        strip[3].r = 4;
+        strip[4].r = 5;
+
+        strip >>= 1;
+
+        assert_eq!(strip[0].r, 5);
+        assert_eq!(strip[1].r, 1);
+        assert_eq!(strip[2].r, 2);
+        assert_eq!(strip[3].r, 3);
+        assert_eq!(strip[4].r, 4);
+    }
+
+    #[test]
+    fn test_shift_left() {
+        let mut strip = Strip::new_mock(5);
+
+        strip.leds[0].r = 1;
+        strip.leds[1].r = 2;
+        strip.leds[2].r = 3;
+        strip.leds[3].r = 4;
+        strip.leds[4].r = 5;
 
         strip <<= 1;
 
@@ -177,4 +2367,360 @@ mod tests {
         assert_eq!(strip.leds[3].r, 5);
         assert_eq!(strip.leds[4].r, 1);
     }
+
+    #[test]
+    fn test_get_and_set() {
+        let mut strip = Strip::new_mock(5);
+        let led = Led::from_rgbw(10, 20, 30, 40);
+
+        assert_eq!(strip.set(4, led), Ok(()));
+        assert_eq!(strip.get(4), Some(led));
+        assert_eq!(strip.get(0), Some(Led::new()));
+
+        assert_eq!(strip.set(5, led), Err(IndexError { index: 5, len: 5 }));
+        assert_eq!(strip.get(5), None);
+    }
+
+    #[test]
+    fn test_gamma_correction() {
+        let mut strip = Strip::new_mock(1);
+        let led = Led::from_rgbw(128, 128, 128, 128);
+        strip.fill(led);
+
+        let corrected = strip.output_color(&led);
+        assert!(corrected.r < 128);
+        assert_eq!(
+            strip.raw_led_data().collect::<Vec<u8>>(),
+            corrected.to_raw_led_bytes()
+        );
+        assert_eq!(strip.leds[0], led);
+
+        strip.set_gamma(1.0);
+        assert_eq!(
+            strip.raw_led_data().collect::<Vec<u8>>(),
+            led.to_raw_led_bytes()
+        );
+    }
+
+    #[test]
+    fn test_color_order() {
+        let mut strip = Strip::new_mock(1);
+        strip.fill(Led::from_rgbw(0xAA, 0x00, 0xFF, 0x33));
+
+        let grbw_data: Vec<u8> = strip.raw_led_data().collect();
+        strip.set_color_order(ColorOrder::Rgbw);
+        let rgbw_data: Vec<u8> = strip.raw_led_data().collect();
+
+        assert_eq!(strip.color_order(), ColorOrder::Rgbw);
+        assert_ne!(grbw_data, rgbw_data);
+    }
+
+    #[test]
+    fn test_brightness() {
+        let mut strip = Strip::new_mock(1);
+        let led = Led::from_rgbw(200, 100, 50, 255);
+        strip.fill(led);
+        strip.set_gamma(1.0);
+        strip.set_brightness(128);
+
+        assert_eq!(
+            strip.raw_led_data().collect::<Vec<u8>>(),
+            Led::from_rgbw(100, 50, 25, 128).to_raw_led_bytes()
+        );
+        assert_eq!(strip.leds[0], led);
+    }
+
+    #[test]
+    fn test_transmit_buffer_reuse() {
+        let mut strip = make_strip();
+
+        strip.encode_buffer();
+        let buffer_ptr = strip.buffer.as_ptr();
+        let buffer_capacity = strip.buffer.capacity();
+        assert_eq!(strip.buffer.len(), strip.leds.len() * 32);
+
+        strip.fill(Led::from_rgbw(10, 20, 30, 40));
+        strip.encode_buffer();
+        assert_eq!(strip.buffer.as_ptr(), buffer_ptr);
+        assert_eq!(strip.buffer.capacity(), buffer_capacity);
+        assert_eq!(strip.buffer, strip.raw_led_data().collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn test_nonblocking_update() {
+        let mut strip = make_strip();
+        assert!(strip.is_ready(Instant::now()));
+
+        let ready_at = strip.update_nonblocking().unwrap();
+        assert!(!strip.is_ready(ready_at - Duration::from_micros(1)));
+        assert!(strip.is_ready(ready_at));
+    }
+
+    #[test]
+    fn test_estimated_current() {
+        let mut strip = make_strip();
+        let leds_count = strip.leds.len() as u32;
+
+        strip.clear();
+        assert_eq!(
+            strip.estimated_current_ma(),
+            leds_count * QUIESCENT_CURRENT_MA
+        );
+
+        strip.fill(Led::from_rgbw(255, 255, 255, 255));
+        assert_eq!(
+            strip.estimated_current_ma(),
+            leds_count
+                * (QUIESCENT_CURRENT_MA
+                    + RED_CURRENT_MA
+                    + GREEN_CURRENT_MA
+                    + BLUE_CURRENT_MA
+                    + WHITE_CURRENT_MA)
+        );
+    }
+
+    #[test]
+    fn test_limit_power() {
+        let mut strip = make_strip();
+        let white = Led::from_rgbw(255, 255, 255, 255);
+
+        strip.fill(white);
+        let current = strip.estimated_current_ma();
+        strip.limit_power(current);
+        assert!(strip.leds.iter().all(|led| *led == white));
+
+        strip.limit_power(2000);
+        assert!(strip.estimated_current_ma() <= 2000);
+        assert!(strip.leds.iter().all(|led| *led != white));
+    }
+
+    #[test]
+    fn test_rgb_pixel_format() {
+        let mut strip = Strip::new_mock(2);
+        let led = Led::from_rgbw(0xAA, 0x00, 0xFF, 0x33);
+        strip.fill(led);
+        strip.set_gamma(1.0);
+        strip.set_pixel_format(PixelFormat::Rgb);
+
+        let raw_data: Vec<u8> = strip.raw_led_data().collect();
+        assert_eq!(raw_data.len(), 2 * 24);
+        assert_eq!(raw_data[..24], led.to_raw_led_bytes()[..24]);
+    }
+
+    #[test]
+    fn test_strip_error_from_index_error() {
+        let error: StripError<Infallible> = IndexError { index: 5, len: 5 }.into();
+
+        assert!(matches!(
+            error,
+            StripError::OutOfBounds(IndexError { index: 5, len: 5 })
+        ));
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn test_update_with_spi_bus() {
+        let led = Led::from_rgbw(0xAA, 0x00, 0xFF, 0x33);
+        let mut strip = Strip::from_spi_bus(MockSpi::new(), 2);
+        strip.set_gamma(1.0);
+        strip.fill(led);
+
+        strip.update().unwrap();
+
+        assert_eq!(
+            strip.spi.last_frame(),
+            [led.to_raw_led_bytes(), led.to_raw_led_bytes()].concat()
+        );
+    }
+
+    #[test]
+    fn test_white_balance() {
+        let mut strip = Strip::new_mock(1);
+        strip.fill(Led::from_rgbw(200, 100, 50, 20));
+        let unbalanced_data: Vec<u8> = strip.raw_led_data().collect();
+
+        strip.set_white_balance(1.0, 1.0, 1.0);
+        assert_eq!(strip.raw_led_data().collect::<Vec<u8>>(), unbalanced_data);
+
+        strip.set_gamma(1.0);
+        strip.set_white_balance(0.5, 1.0, 0.0);
+        assert_eq!(
+            strip.raw_led_data().collect::<Vec<u8>>(),
+            Led::from_rgbw(100, 100, 0, 20).to_raw_led_bytes()
+        );
+    }
+
+    #[test]
+    fn test_update_at_fps() {
+        let mut strip = make_strip();
+        let frame_interval = Duration::from_millis(20);
+
+        let mut last_frame = Instant::now();
+        let start = Instant::now();
+        strip.update_at_fps(50, &mut last_frame).unwrap();
+        assert!(start.elapsed() >= frame_interval);
+
+        let mut last_frame = Instant::now() - Duration::from_secs(1);
+        let start = Instant::now();
+        strip.update_at_fps(50, &mut last_frame).unwrap();
+        assert!(start.elapsed() < frame_interval);
+        assert!(last_frame >= start);
+    }
+
+    #[test]
+    fn test_per_channel_brightness() {
+        let mut strip = Strip::new_mock(1);
+        strip.fill(Led::from_rgbw(200, 100, 50, 255));
+        strip.set_gamma(1.0);
+
+        strip.set_white_brightness(0);
+        assert_eq!(
+            strip.raw_led_data().collect::<Vec<u8>>(),
+            Led::from_rgbw(200, 100, 50, 0).to_raw_led_bytes()
+        );
+
+        strip.set_white_brightness(u8::MAX);
+        strip.set_rgb_brightness(0);
+        assert_eq!(
+            strip.raw_led_data().collect::<Vec<u8>>(),
+            Led::from_rgbw(0, 0, 0, 255).to_raw_led_bytes()
+        );
+    }
+
+    #[test]
+    fn test_dithering() {
+        let mut strip = Strip::new_mock(1);
+        strip.set_gamma(1.0);
+        strip.set_brightness(10);
+        strip.fill(Led::from_rgb(100, 0, 0));
+
+        // 100 * 10 / 255 = ~3.92, which is truncated to 3 without dithering
+        let low = Led::from_rgb(3, 0, 0).to_raw_led_bytes();
+        let high = Led::from_rgb(4, 0, 0).to_raw_led_bytes();
+        strip.update().unwrap();
+        assert_eq!(strip.last_frame(), low);
+
+        strip.enable_dithering(true);
+        let frames = 255;
+        let mut sum = 0;
+        for _ in 0..frames {
+            strip.update_nonblocking().unwrap();
+            let frame = strip.last_frame();
+            assert!(frame == low || frame == high);
+            sum += if frame == high { 4 } else { 3 };
+        }
+
+        let average = sum as f32 / frames as f32;
+        assert!((average - 100.0 * 10.0 / 255.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_chain() {
+        let first_led = Led::from_rgb(1, 2, 3);
+        let second_led = Led::from_rgb(4, 5, 6);
+        let mut first = Strip::new_mock(3);
+        let mut second = Strip::new_mock(2);
+        first.fill(first_led);
+        second.fill(second_led);
+
+        let first_len = first.len();
+        let mut strip = first.chain(second);
+        assert_eq!(strip.len(), 5);
+        assert_eq!(strip[first_len], second_led);
+
+        strip[first_len] = Led::from_rgb(7, 8, 9);
+        strip.set_gamma(1.0);
+        strip.update().unwrap();
+
+        let frame = strip.last_frame();
+        assert_eq!(frame.len(), 5 * 32);
+        assert_eq!(frame[..32], first_led.to_raw_led_bytes());
+        assert_eq!(
+            frame[3 * 32..4 * 32],
+            Led::from_rgb(7, 8, 9).to_raw_led_bytes()
+        );
+        assert_eq!(frame[4 * 32..], second_led.to_raw_led_bytes());
+    }
+
+    #[test]
+    fn test_builder() {
+        let strip = StripBuilder::new()
+            .leds(12)
+            .color_order(ColorOrder::Rgbw)
+            .pixel_format(PixelFormat::Rgb)
+            .gamma(1.0)
+            .build_with_spi_bus(MockSpi::new());
+
+        assert_eq!(strip.len(), 12);
+        assert_eq!(strip.color_order(), ColorOrder::Rgbw);
+        assert_eq!(strip.pixel_format(), PixelFormat::Rgb);
+        assert_eq!(strip.gamma(), 1.0);
+        assert_eq!(strip.brightness(), u8::MAX);
+    }
+
+    #[test]
+    fn test_frame_too_large() {
+        let mut strip = Strip::new_mock(3);
+        strip.set_max_frame_size(Some(64));
+
+        let error = strip.update().unwrap_err();
+        assert!(matches!(
+            error,
+            StripError::FrameTooLarge {
+                needed: 96,
+                max: 64
+            }
+        ));
+        assert_eq!(
+            error.to_string(),
+            "frame of 96 bytes exceeds maximal frame size of 64 bytes (set with Strip::set_max_frame_size or read from spidev.bufsiz)"
+        );
+        assert!(strip.last_frame().is_empty());
+
+        strip.set_pixel_format(PixelFormat::Rgb);
+        strip.set_max_frame_size(Some(72));
+        strip.update().unwrap();
+
+        strip.set_max_frame_size(None);
+        strip.set_pixel_format(PixelFormat::Rgbw);
+        strip.update().unwrap();
+        assert_eq!(strip.last_frame().len(), 96);
+    }
+
+    #[test]
+    #[cfg(feature = "rppal")]
+    fn test_read_spi_buffer_size() {
+        let path = std::env::temp_dir().join(format!("sk6812_bufsiz_{}", std::process::id()));
+
+        std::fs::write(&path, "65535\n").unwrap();
+        assert_eq!(read_spi_buffer_size(&path), Some(65535));
+
+        std::fs::write(&path, "not a number").unwrap();
+        assert_eq!(read_spi_buffer_size(&path), None);
+
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read_spi_buffer_size(&path), None);
+    }
+
+    #[test]
+    fn test_frame_splitting() {
+        let mut strip = Strip::new_mock(10);
+        strip.set_gamma(1.0);
+        strip.for_each_indexed(|index, led| *led = Led::from_rgbw(index as u8, 1, 2, 3));
+        let expected = strip.encode();
+
+        strip.update().unwrap();
+        assert_eq!(strip.last_frame_writes(), 1);
+
+        // 100 bytes fit 3 LEDs, so 10 LEDs are sent in 4 writes
+        strip.set_max_transfer_size(Some(100));
+        strip.update().unwrap();
+        assert_eq!(strip.last_frame_writes(), 4);
+        assert_eq!(strip.last_frame(), expected);
+
+        strip.set_max_transfer_size(Some(1));
+        strip.update().unwrap();
+        assert_eq!(strip.last_frame_writes(), 10);
+        assert_eq!(strip.last_frame(), expected);
+    }
 }
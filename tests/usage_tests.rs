@@ -1,7 +1,9 @@
 use std::{error::Error, thread, time::Duration};
 
 use palette::{FromColor, Gradient, Hsl, Hsv, LinSrgb, Srgb};
+use rppal::spi::{Bus, Mode, SlaveSelect, Spi};
 use sk6812_rpi::led::Led;
+use sk6812_rpi::strip::Strip;
 
 mod common;
 
@@ -85,7 +87,7 @@ fn test_strip_gradient_shifting() -> Result<(), Box<dyn Error>> {
     strip.set_gradient(Gradient::new(colors));
     strip.update()?;
 
-    (0..strip.leds.len()).for_each(|_| {
+    (0..strip.len()).for_each(|_| {
         strip <<= 1;
         strip.update().unwrap();
         thread::sleep(shift_delay);
@@ -153,3 +155,25 @@ fn test_led_iter_manipulation() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[test]
+fn test_led_for_each_indexed() -> Result<(), Box<dyn Error>> {
+    let mut strip = common::make_strip();
+
+    strip.for_each_indexed(|index, led| led.w = index as u8);
+
+    strip.update()?;
+
+    Ok(())
+}
+
+#[test]
+fn test_strip_from_spi() -> Result<(), Box<dyn Error>> {
+    let spi = Spi::new(Bus::Spi0, SlaveSelect::Ss0, 6_400_000, Mode::Mode0)?;
+    let mut strip = Strip::from_spi(spi, 144);
+
+    strip.fill(Led::from_rgb(0, 100, 150));
+    strip.update()?;
+
+    Ok(())
+}